@@ -1,11 +1,12 @@
-use conways_game_of_life_impl::{config, Grid, Grid1dVec, Grid2dArr, Grid2dVec, GridUnit};
+use conways_game_of_life_impl::{
+    config, Backend, Grid, Grid1dVec, Grid2dArr, Grid2dVec, GridBitPacked, GridChunked, GridUnit,
+};
 use criterion::{
     criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
 };
-use std::time::Duration;
+use std::{thread, time::Duration};
 
 fn init_benchmark<G: Grid>(name: &str, mut grid: G, group: &mut BenchmarkGroup<'_, WallTime>) {
-    grid.set_fps(0);
     config::random(&mut grid, 0.25).unwrap();
 
     group.bench_function(name, |b| {
@@ -15,6 +16,21 @@ fn init_benchmark<G: Grid>(name: &str, mut grid: G, group: &mut BenchmarkGroup<'
     });
 }
 
+fn init_parallel_benchmark<G: Grid>(
+    name: &str,
+    mut grid: G,
+    group: &mut BenchmarkGroup<'_, WallTime>,
+) {
+    config::random(&mut grid, 0.25).unwrap();
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            grid.update_parallel(threads);
+        })
+    });
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("random");
     group.measurement_time(Duration::from_secs(10));
@@ -31,7 +47,28 @@ fn criterion_benchmark(c: &mut Criterion) {
         Grid2dArr::<WIDTH, HEIGHT>::empty(),
         &mut group,
     );
+    init_benchmark("bit_packed", GridBitPacked::empty(size), &mut group);
+    init_benchmark("chunked", GridChunked::empty(size), &mut group);
+    init_parallel_benchmark("1d_vec_parallel", Grid1dVec::empty(size), &mut group);
+    init_parallel_benchmark(
+        "bit_packed_parallel",
+        GridBitPacked::empty(size),
+        &mut group,
+    );
+    for backend in Backend::ALL {
+        init_benchmark(&format!("{}_dyn", backend), backend.empty(size), &mut group);
+    }
+
+    group.finish();
 
+    /*A grid big enough for the threads to pay for themselves, updated both ways.*/
+    let mut group = c.benchmark_group("parallel");
+    group.measurement_time(Duration::from_secs(10));
+    let size = (2000, 2000);
+    init_benchmark("1d_vec", Grid1dVec::empty(size), &mut group);
+    init_parallel_benchmark("1d_vec_parallel", Grid1dVec::empty(size), &mut group);
+    init_benchmark("2d_vec", Grid2dVec::empty(size), &mut group);
+    init_parallel_benchmark("2d_vec_parallel", Grid2dVec::empty(size), &mut group);
     group.finish();
 }
 
@@ -0,0 +1,72 @@
+use crate::{DynGrid, Grid1dVec, Grid2dVec, GridBitPacked, GridChunked, GridPoint};
+use std::{error::Error, fmt, str::FromStr};
+
+/*The grid backends whose size can be chosen at runtime. Grid2dArr is left out, as its size
+is part of its type.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Backend {
+    #[default]
+    Vec1d,
+    Vec2d,
+    BitPacked,
+    Chunked,
+}
+
+impl Backend {
+    pub const ALL: [Self; 4] = [Self::Vec1d, Self::Vec2d, Self::BitPacked, Self::Chunked];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Vec1d => "1d_vec",
+            Self::Vec2d => "2d_vec",
+            Self::BitPacked => "bit_packed",
+            Self::Chunked => "chunked",
+        }
+    }
+
+    pub fn empty(&self, size: GridPoint) -> DynGrid {
+        match self {
+            Self::Vec1d => Box::new(Grid1dVec::empty(size)),
+            Self::Vec2d => Box::new(Grid2dVec::empty(size)),
+            Self::BitPacked => Box::new(GridBitPacked::empty(size)),
+            Self::Chunked => Box::new(GridChunked::empty(size)),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownBackend {
+    name: String,
+}
+impl UnknownBackend {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+impl fmt::Display for UnknownBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl Error for UnknownBackend {}
+
+/*Parses the names given by Backend::name.*/
+impl FromStr for Backend {
+    type Err = UnknownBackend;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|backend| backend.name() == s)
+            .ok_or_else(|| UnknownBackend {
+                name: s.to_string(),
+            })
+    }
+}
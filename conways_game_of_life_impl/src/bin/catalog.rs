@@ -0,0 +1,30 @@
+use conways_game_of_life_impl::{catalog, Grid, Grid2dVec, Simulation, StampMode, Topology};
+use std::{env, error::Error, io};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let entry = match env::args().nth(1) {
+        Some(name) => match catalog::find(&name) {
+            Some(entry) => entry,
+            None => {
+                println!("Unknown pattern: {}", name);
+                return Ok(catalog::write_list(io::stdout())?);
+            }
+        },
+        None => return Ok(catalog::write_list(io::stdout())?),
+    };
+
+    let pattern = entry.pattern();
+    let (width, height) = pattern.size();
+    let mut grid = Grid2dVec::empty((width.max(60) + 20, height.max(20) + 4));
+    grid.set_topology(Topology::Plane);
+    grid.stamp(&pattern, (10, 2), StampMode::Or)?;
+
+    let mut simulation = Simulation::new(grid);
+    simulation.set_gps(10);
+    loop {
+        print!("{esc}c", esc = 27 as char);
+        println!("{} ({})", entry.name(), entry.category());
+        println!("{}", simulation.grid());
+        simulation.frame();
+    }
+}
@@ -0,0 +1,288 @@
+use conways_game_of_life_impl::{
+    format::{self, Format, PatternFile},
+    Backend, Density, GenerationStats, Grid, GridPoint, GridUnit, History, Repeat, Rule, Soup,
+    StatsSeries, Topology,
+};
+use std::{env, error::Error, fs, process};
+
+const USAGE: &str = "\
+Usage: life [OPTIONS] [PATTERN_FILE]
+
+Runs a pattern file, or a random soup when none is given, and writes the final pattern
+cropped to its bounding box, with a summary of the run in its comments.
+
+Options:
+  -s, --size WxH           grid size [default: 256x256]
+  -r, --rule RULE          rule such as B36/S23 [default: the file's rule, or B3/S23]
+  -t, --topology NAME      torus, plane, klein_bottle or cross_surface [default: torus]
+  -b, --backend NAME       1d_vec, 2d_vec, bit_packed or chunked [default: 1d_vec]
+  -n, --generations N      generations to run [default: 1000]
+  -u, --until-stable       stop early once the grid repeats itself
+      --seed N             seed of the soup [default: random]
+      --density D          chance from 0 to 1 of each soup cell being alive [default: 0.5]
+  -f, --format NAME        rle, plaintext, life105 or life106 [default: rle]
+  -o, --output FILE        write the pattern to FILE instead of stdout
+      --stats FILE         write the population of every generation to FILE as CSV
+  -h, --help               print this message
+
+Exits with 0 on success, 1 if the run failed and 2 if the arguments are invalid.";
+
+enum Failure {
+    Usage(String),
+    Run(Box<dyn Error>),
+}
+
+impl<E: Error + 'static> From<E> for Failure {
+    fn from(e: E) -> Self {
+        Self::Run(Box::new(e))
+    }
+}
+
+struct Options {
+    input: Option<String>,
+    size: GridPoint,
+    rule: Option<Rule>,
+    topology: Topology,
+    backend: Backend,
+    generations: u64,
+    until_stable: bool,
+    seed: Option<u64>,
+    density: f64,
+    format: Format,
+    output: Option<String>,
+    stats: Option<String>,
+}
+
+fn usage<T>(message: String) -> Result<T, Failure> {
+    Err(Failure::Usage(message))
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, Failure> {
+    value
+        .parse()
+        .or_else(|_| usage(format!("invalid value for {}: {}", flag, value)))
+}
+
+fn parse_size(value: &str) -> Result<GridPoint, Failure> {
+    let parse = |s: &str| s.parse::<GridUnit>().ok().filter(|&n| n > 0);
+    match value.split_once('x') {
+        Some((width, height)) => match (parse(width), parse(height)) {
+            (Some(width), Some(height)) => Ok((width, height)),
+            _ => usage(format!("invalid size: {}", value)),
+        },
+        None => usage(format!("invalid size: {}", value)),
+    }
+}
+
+fn parse_density(value: &str) -> Result<f64, Failure> {
+    match value.parse::<f64>() {
+        Ok(density) if (0. ..=1.).contains(&density) => Ok(density),
+        _ => usage(format!("density must be between 0 and 1: {}", value)),
+    }
+}
+
+fn parse_topology(value: &str) -> Result<Topology, Failure> {
+    Ok(match value {
+        "torus" => Topology::Torus,
+        "plane" => Topology::Plane,
+        "klein_bottle" => Topology::KleinBottle,
+        "cross_surface" => Topology::CrossSurface,
+        _ => return usage(format!("unknown topology: {}", value)),
+    })
+}
+
+fn parse_format(value: &str) -> Result<Format, Failure> {
+    Ok(match value {
+        "rle" => Format::Rle,
+        "plaintext" => Format::Plaintext,
+        "life105" => Format::Life105,
+        "life106" => Format::Life106,
+        _ => return usage(format!("unknown format: {}", value)),
+    })
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, Failure> {
+    let mut options = Options {
+        input: None,
+        size: (256, 256),
+        rule: None,
+        topology: Topology::default(),
+        backend: Backend::default(),
+        generations: 1000,
+        until_stable: false,
+        seed: None,
+        density: 0.5,
+        format: Format::Rle,
+        output: None,
+        stats: None,
+    };
+    while let Some(arg) = args.next() {
+        let flag = arg.as_str();
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| Failure::Usage(format!("missing value for {}", flag)))
+        };
+        match flag {
+            "-h" | "--help" => return Ok(None),
+            "-s" | "--size" => options.size = parse_size(&value()?)?,
+            "-r" | "--rule" => options.rule = Some(parse_value(flag, &value()?)?),
+            "-t" | "--topology" => options.topology = parse_topology(&value()?)?,
+            "-b" | "--backend" => options.backend = parse_value(flag, &value()?)?,
+            "-n" | "--generations" => options.generations = parse_value(flag, &value()?)?,
+            "-u" | "--until-stable" => options.until_stable = true,
+            "--seed" => options.seed = Some(parse_value(flag, &value()?)?),
+            "--density" => options.density = parse_density(&value()?)?,
+            "-f" | "--format" => options.format = parse_format(&value()?)?,
+            "-o" | "--output" => options.output = Some(value()?),
+            "--stats" => options.stats = Some(value()?),
+            _ if flag.starts_with('-') => return usage(format!("unknown option: {}", flag)),
+            _ if options.input.is_none() => options.input = Some(arg),
+            _ => return usage(format!("unexpected argument: {}", flag)),
+        }
+    }
+    Ok(Some(options))
+}
+
+fn run(options: Options) -> Result<(), Failure> {
+    let mut grid = options.backend.empty(options.size);
+    grid.set_topology(options.topology);
+    let mut comments = Vec::new();
+    match &options.input {
+        Some(path) => {
+            let pattern = format::parse(&fs::read_to_string(path)?)?;
+            let top_left = (
+                options.size.0.saturating_sub(pattern.size.0) / 2,
+                options.size.1.saturating_sub(pattern.size.1) / 2,
+            );
+            pattern.place(&mut grid, top_left)?;
+            if let Some(rule) = pattern.rule {
+                grid.set_rule(rule);
+            }
+            comments.push(format!("Source: {}", path));
+        }
+        None => {
+            let mut soup = Soup::new().density(Density::Uniform(options.density));
+            if let Some(seed) = options.seed {
+                soup = soup.seed(seed);
+            }
+            let seed = soup.fill(&mut grid)?;
+            comments.push(format!(
+                "Source: soup, seed {}, density {}",
+                seed, options.density
+            ));
+        }
+    }
+    if let Some(rule) = options.rule {
+        grid.set_rule(rule);
+    }
+
+    let mut series = StatsSeries::new();
+    series.push(GenerationStats::from_grid(&grid));
+    let mut history = History::new();
+    let mut repeat: Option<Repeat> = None;
+    if options.until_stable {
+        history.record(&grid);
+    }
+    for _ in 0..options.generations {
+        series.update(&mut grid);
+        if options.until_stable {
+            repeat = history.record(&grid);
+            if repeat.is_some() {
+                break;
+            }
+        }
+    }
+
+    comments.push(format!(
+        "Rule: {}, topology: {:?}, grid: {}x{}",
+        grid.rule(),
+        grid.topology(),
+        options.size.0,
+        options.size.1
+    ));
+    comments.push(format!("Generations: {}", grid.generation()));
+    if options.until_stable {
+        comments.push(match repeat {
+            Some(repeat) if repeat.is_translated() => format!(
+                "Period: {} from generation {}, moving {:?}",
+                repeat.period(),
+                repeat.start(),
+                repeat.offset()
+            ),
+            Some(repeat) => format!(
+                "Period: {} from generation {}",
+                repeat.period(),
+                repeat.start()
+            ),
+            None => String::from("Period: none found"),
+        });
+    }
+    let stats = series.as_slice();
+    if let (Some(first), Some(last)) = (stats.first(), stats.last()) {
+        let min = stats
+            .iter()
+            .min_by_key(|stats| stats.population())
+            .unwrap_or(first);
+        let max = stats
+            .iter()
+            .max_by_key(|stats| stats.population())
+            .unwrap_or(first);
+        comments.push(format!(
+            "Population: final {}, min {} at generation {}, max {} at generation {}",
+            last.population(),
+            min.population(),
+            min.generation(),
+            max.population(),
+            max.generation()
+        ));
+    }
+
+    /*The series always holds at least the starting generation, and its last entry is the
+    grid as it is now.*/
+    let bounding_box = series.last().and_then(|stats| stats.bounding_box());
+    let mut pattern = match bounding_box {
+        Some((min, max)) => {
+            comments.push(format!("Bounding box: {:?} to {:?}", min, max));
+            PatternFile::from_grid_region(&grid, min, (max.0 - min.0 + 1, max.1 - min.1 + 1))?
+        }
+        None => {
+            comments.push(String::from("Bounding box: none"));
+            PatternFile {
+                rule: Some(grid.rule()),
+                ..PatternFile::default()
+            }
+        }
+    };
+    pattern.comments = comments;
+
+    let written = options.format.write(&pattern);
+    match &options.output {
+        Some(path) => fs::write(path, written)?,
+        None => print!("{}", written),
+    }
+    if let Some(path) = &options.stats {
+        series.write_csv(fs::File::create(path)?)?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let code = match parse_args(env::args().skip(1)).and_then(|options| match options {
+        Some(options) => run(options),
+        None => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }) {
+        Ok(()) => 0,
+        Err(Failure::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            2
+        }
+        Err(Failure::Run(e)) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    };
+    process::exit(code);
+}
@@ -2,7 +2,6 @@ use conways_game_of_life_impl::{BResult, Grid, Grid2dArr};
 
 fn main() -> BResult<()> {
     let mut grid = Grid2dArr::<80, 21>::empty();
-    grid.set_fps(10);
     grid.set_line((0, 0), (5, 5), true)?;
     grid.set_line((2, 0), (20, 1), true)?;
     grid.set_line((0, 2), (1, 20), true)?;
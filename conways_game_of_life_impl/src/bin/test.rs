@@ -1,13 +1,14 @@
-use conways_game_of_life_impl::{config, BResult, Grid, Grid2dArr};
+use conways_game_of_life_impl::{config, BResult, Grid2dArr, Simulation};
 
 fn main() -> BResult<()> {
     let mut grid = Grid2dArr::<80, 21>::empty();
-    grid.set_fps(10);
     config::test(&mut grid)?;
 
+    let mut simulation = Simulation::new(grid);
+    simulation.set_gps(10);
     loop {
         print!("{esc}c", esc = 27 as char);
-        println!("{}", grid);
-        grid.update();
+        println!("{}", simulation.grid());
+        simulation.frame();
     }
 }
@@ -0,0 +1,324 @@
+use crate::{
+    stats::StatsCounter, GenerationStats, Grid, GridPoint, GridStorage, GridUnit, Rule, Topology,
+};
+use std::{fmt, mem, thread};
+
+type Word = u64;
+const WORD_BITS: usize = Word::BITS as usize;
+
+#[derive(Clone)]
+pub struct GridBitPacked {
+    size: GridPoint,
+    row_words: usize,
+    current_words: Vec<Word>,
+    next_words: Vec<Word>,
+    rule: Rule,
+    topology: Topology,
+    generation: u64,
+}
+
+impl GridBitPacked {
+    pub fn empty(size: GridPoint) -> Self {
+        let row_words = (size.0 as usize).div_ceil(WORD_BITS);
+        let next_words = vec![0; row_words * size.1 as usize];
+        Self {
+            size,
+            row_words,
+            current_words: next_words.clone(),
+            next_words,
+            rule: Rule::default(),
+            topology: Topology::default(),
+            generation: 0,
+        }
+    }
+
+    fn get_index(&self, (x, y): GridPoint) -> (usize, Word) {
+        (
+            y as usize * self.row_words + x as usize / WORD_BITS,
+            1 << (x as usize % WORD_BITS),
+        )
+    }
+
+    fn get_mapped_cell(&self, point: (i32, i32)) -> bool {
+        self.topology
+            .map_point(point, self.size)
+            .is_some_and(|point| self.get_cell_unchecked(point))
+    }
+
+    /*Words for the rows just above and below the grid, plus the cells just left and right
+    of every row (including those two), resolved through the topology once per generation
+    so that every row can then be computed a whole word (64 cells) at a time.*/
+    fn ghost_cells(&self) -> (Vec<Word>, Vec<Word>, Vec<(bool, bool)>) {
+        let width = self.size.0 as i32;
+        let height = self.size.1 as i32;
+        let ghost_row = |y| {
+            let mut words = vec![0; self.row_words];
+            for x in 0..width {
+                if self.get_mapped_cell((x, y)) {
+                    words[x as usize / WORD_BITS] |= 1 << (x as usize % WORD_BITS);
+                }
+            }
+            words
+        };
+        let ghost_columns = (-1..=height)
+            .map(|y| {
+                (
+                    self.get_mapped_cell((-1, y)),
+                    self.get_mapped_cell((width, y)),
+                )
+            })
+            .collect();
+        (ghost_row(-1), ghost_row(height), ghost_columns)
+    }
+
+    fn rule_table(&self) -> Vec<([Word; 4], Word, Word)> {
+        (0..=8)
+            .filter(|&n| self.rule.birth(n) || self.rule.survival(n))
+            .map(|n| {
+                let mask = |b| if b { Word::MAX } else { 0 };
+                let count_masks = [0, 1, 2, 3].map(|bit| mask(n >> bit & 1 == 1));
+                (
+                    count_masks,
+                    mask(self.rule.birth(n)),
+                    mask(self.rule.survival(n)),
+                )
+            })
+            .collect()
+    }
+
+    /*Rows are independent once the ghost cells are known, so with more than one thread
+    the next buffer is split into bands of whole rows, each written by its own thread.*/
+    fn set_next_state_swar(&mut self, threads: usize) -> StatsCounter {
+        let row_words = self.row_words;
+        let height = self.size.1 as usize;
+        let rule_table = self.rule_table();
+        let (top, bottom, ghost_columns) = self.ghost_cells();
+        let swar = Swar {
+            current: &self.current_words,
+            top: &top,
+            bottom: &bottom,
+            ghost_columns: &ghost_columns,
+            rule_table: &rule_table,
+            row_words,
+            height,
+            last_bit: (self.size.0 as usize + WORD_BITS - 1) % WORD_BITS,
+        };
+
+        let next_words = &mut self.next_words;
+        let band_height = height.div_ceil(threads.max(1)).max(1);
+        if band_height >= height {
+            return swar.set_rows(0, next_words);
+        }
+        thread::scope(|scope| {
+            let handles: Vec<_> = next_words
+                .chunks_mut(band_height * row_words)
+                .enumerate()
+                .map(|(i, band)| {
+                    let swar = &swar;
+                    scope.spawn(move || swar.set_rows(i * band_height, band))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .fold(StatsCounter::default(), |mut counter, handle| {
+                    counter.merge(&handle.join().unwrap());
+                    counter
+                })
+        })
+    }
+}
+
+struct Swar<'a> {
+    current: &'a [Word],
+    top: &'a [Word],
+    bottom: &'a [Word],
+    ghost_columns: &'a [(bool, bool)],
+    rule_table: &'a [([Word; 4], Word, Word)],
+    row_words: usize,
+    height: usize,
+    last_bit: usize,
+}
+
+impl Swar<'_> {
+    /*Row y + 1 of the padded grid, where rows 0 and height + 1 are the ghost rows.*/
+    fn padded_row(&self, y: usize) -> (&[Word], (bool, bool)) {
+        let row = match y {
+            0 => self.top,
+            y if y == self.height + 1 => self.bottom,
+            y => &self.current[(y - 1) * self.row_words..y * self.row_words],
+        };
+        (row, self.ghost_columns[y])
+    }
+
+    fn set_rows(&self, first_row: usize, next_rows: &mut [Word]) -> StatsCounter {
+        let mut counter = StatsCounter::default();
+        let last_word_mask = Word::MAX >> (WORD_BITS - 1 - self.last_bit);
+        for (y, next_row) in (first_row..).zip(next_rows.chunks_mut(self.row_words)) {
+            let above = self.padded_row(y);
+            let row = self.padded_row(y + 1);
+            let below = self.padded_row(y + 2);
+            for (i, next) in next_row.iter_mut().enumerate() {
+                let (north_west, north, north_east) = shifted(above, i, self.last_bit);
+                let (west, alive, east) = shifted(row, i, self.last_bit);
+                let (south_west, south, south_east) = shifted(below, i, self.last_bit);
+                let count = count_neighbours([
+                    north_west, north, north_east, west, east, south_west, south, south_east,
+                ]);
+
+                let mut word = 0;
+                for &(count_masks, birth, survival) in self.rule_table {
+                    word |= count_equals(count, count_masks) & (birth & !alive | survival & alive);
+                }
+                if i == self.row_words - 1 {
+                    word &= last_word_mask;
+                }
+                *next = word;
+                counter.count_word(((i * WORD_BITS) as GridUnit, y as GridUnit), alive, word);
+            }
+        }
+        counter
+    }
+}
+
+fn shifted(
+    (row, ghosts): (&[Word], (bool, bool)),
+    i: usize,
+    last_bit: usize,
+) -> (Word, Word, Word) {
+    let word = row[i];
+    let west = word << 1
+        | if i > 0 {
+            row[i - 1] >> (WORD_BITS - 1)
+        } else {
+            ghosts.0 as Word
+        };
+    let east = word >> 1
+        | if i + 1 < row.len() {
+            row[i + 1] << (WORD_BITS - 1)
+        } else {
+            (ghosts.1 as Word) << last_bit
+        };
+    (west, word, east)
+}
+
+fn half_add(a: Word, b: Word) -> (Word, Word) {
+    (a ^ b, a & b)
+}
+
+fn full_add(a: Word, b: Word, c: Word) -> (Word, Word) {
+    let (sum, carry_0) = half_add(a, b);
+    let (sum, carry_1) = half_add(sum, c);
+    (sum, carry_0 | carry_1)
+}
+
+/*Sums eight neighbour words into four bit planes (1s, 2s, 4s and 8s) with a tree of
+adders, so every bit position holds that cell's neighbour count.*/
+fn count_neighbours([a, b, c, d, e, f, g, h]: [Word; 8]) -> [Word; 4] {
+    let (ones_0, twos_0) = full_add(a, b, c);
+    let (ones_1, twos_1) = full_add(d, e, f);
+    let (ones_2, twos_2) = half_add(g, h);
+    let (ones, twos_3) = full_add(ones_0, ones_1, ones_2);
+
+    let (twos_4, fours_0) = full_add(twos_0, twos_1, twos_2);
+    let (twos, fours_1) = half_add(twos_4, twos_3);
+    let (fours, eights) = half_add(fours_0, fours_1);
+    [ones, twos, fours, eights]
+}
+
+fn count_equals(count: [Word; 4], count_masks: [Word; 4]) -> Word {
+    !((count[0] ^ count_masks[0])
+        | (count[1] ^ count_masks[1])
+        | (count[2] ^ count_masks[2])
+        | (count[3] ^ count_masks[3]))
+}
+
+impl GridStorage for GridBitPacked {
+    fn _size(&self) -> GridPoint {
+        self.size
+    }
+
+    fn _rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    fn _rule_mut(&mut self) -> &mut Rule {
+        &mut self.rule
+    }
+
+    fn _topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    fn _topology_mut(&mut self) -> &mut Topology {
+        &mut self.topology
+    }
+
+    fn _generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn _generation_mut(&mut self) -> &mut u64 {
+        &mut self.generation
+    }
+
+    fn _get_cell_unchecked(&self, point: GridPoint) -> bool {
+        let (index, mask) = self.get_index(point);
+        self.current_words[index] & mask != 0
+    }
+
+    fn _set_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        let (index, mask) = self.get_index(point);
+        if b {
+            self.current_words[index] |= mask;
+        } else {
+            self.current_words[index] &= !mask;
+        }
+    }
+
+    fn _set_next_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        let (index, mask) = self.get_index(point);
+        if b {
+            self.next_words[index] |= mask;
+        } else {
+            self.next_words[index] &= !mask;
+        }
+    }
+
+    fn _swap_buffers(&mut self) {
+        mem::swap(&mut self.current_words, &mut self.next_words);
+    }
+
+    fn _box_clone(&self) -> Box<dyn Grid> {
+        Box::new(self.clone())
+    }
+}
+
+impl Grid for GridBitPacked {
+    fn update(&mut self) {
+        self.set_next_state_swar(1);
+        self._finish_update();
+    }
+
+    fn update_with_stats(&mut self) -> GenerationStats {
+        let counter = self.set_next_state_swar(1);
+        self._finish_update();
+        counter.finish(self.generation)
+    }
+
+    fn update_parallel(&mut self, threads: usize) {
+        self.set_next_state_swar(threads);
+        self._finish_update();
+    }
+}
+
+impl fmt::Display for GridBitPacked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self._g_fmt('\u{2588}', ' ', f)
+    }
+}
+
+impl fmt::Debug for GridBitPacked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self._g_fmt('X', '-', f)
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{format::rle, Pattern};
+use std::{
+    fmt,
+    io::{self, Write},
+};
+use Category::*;
+use Source::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Category {
+    StillLife,
+    Oscillator,
+    Spaceship,
+    Methuselah,
+    Gun,
+    Puffer,
+    Eater,
+    Reflector,
+}
+
+impl Category {
+    pub const ALL: [Self; 8] = [
+        Self::StillLife,
+        Self::Oscillator,
+        Self::Spaceship,
+        Self::Methuselah,
+        Self::Gun,
+        Self::Puffer,
+        Self::Eater,
+        Self::Reflector,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::StillLife => "still life",
+            Self::Oscillator => "oscillator",
+            Self::Spaceship => "spaceship",
+            Self::Methuselah => "methuselah",
+            Self::Gun => "gun",
+            Self::Puffer => "puffer",
+            Self::Eater => "eater",
+            Self::Reflector => "reflector",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/*Displacement in cells per period in generations, written in the usual notation: c/4 for
+the glider, which moves one cell diagonally every four generations.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Speed {
+    cells: u32,
+    generations: u32,
+}
+impl Speed {
+    pub fn cells(&self) -> u32 {
+        self.cells
+    }
+    pub fn generations(&self) -> u32 {
+        self.generations
+    }
+}
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells = if self.cells != 1 {
+            self.cells.to_string()
+        } else {
+            String::new()
+        };
+        f.pad(&format!("{}c/{}", cells, self.generations))
+    }
+}
+
+enum Source {
+    Constructor(fn() -> Pattern),
+    Rle(&'static str),
+}
+
+/*Period is None for patterns that never repeat (methuselahs) and speed is None for those
+that stay put. Guns and puffers repeat their own cells every period while emitting gliders
+or leaving debris behind.*/
+pub struct Entry {
+    name: &'static str,
+    category: Category,
+    period: Option<u64>,
+    speed: Option<Speed>,
+    discoverer: Option<&'static str>,
+    source: Source,
+}
+
+impl Entry {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    pub fn period(&self) -> Option<u64> {
+        self.period
+    }
+
+    pub fn speed(&self) -> Option<Speed> {
+        self.speed
+    }
+
+    pub fn discoverer(&self) -> Option<&'static str> {
+        self.discoverer
+    }
+
+    pub fn pattern(&self) -> Pattern {
+        match self.source {
+            Source::Constructor(constructor) => constructor(),
+            Source::Rle(s) => rle::parse(s)
+                .ok()
+                .and_then(|file| file.to_pattern().ok())
+                .expect("catalog RLE is valid"),
+        }
+    }
+}
+
+const fn entry(
+    name: &'static str,
+    category: Category,
+    period: Option<u64>,
+    speed: Option<(u32, u32)>,
+    discoverer: Option<&'static str>,
+    source: Source,
+) -> Entry {
+    Entry {
+        name,
+        category,
+        period,
+        speed: match speed {
+            Some((cells, generations)) => Some(Speed { cells, generations }),
+            None => None,
+        },
+        discoverer,
+        source,
+    }
+}
+
+static ENTRIES: [Entry; 22] = [
+    entry(
+        "Block",
+        StillLife,
+        Some(1),
+        None,
+        None,
+        Constructor(Pattern::block),
+    ),
+    entry(
+        "Beehive",
+        StillLife,
+        Some(1),
+        None,
+        None,
+        Constructor(Pattern::bee_hive),
+    ),
+    entry(
+        "Loaf",
+        StillLife,
+        Some(1),
+        None,
+        None,
+        Constructor(Pattern::loaf),
+    ),
+    entry(
+        "Boat",
+        StillLife,
+        Some(1),
+        None,
+        None,
+        Constructor(Pattern::boat),
+    ),
+    entry(
+        "Tub",
+        StillLife,
+        Some(1),
+        None,
+        None,
+        Constructor(Pattern::tub),
+    ),
+    entry(
+        "Blinker",
+        Oscillator,
+        Some(2),
+        None,
+        Some("John Conway"),
+        Constructor(Pattern::blinker),
+    ),
+    entry(
+        "Toad",
+        Oscillator,
+        Some(2),
+        None,
+        Some("Simon Norton"),
+        Constructor(Pattern::toad),
+    ),
+    entry(
+        "Beacon",
+        Oscillator,
+        Some(2),
+        None,
+        Some("John Conway"),
+        Constructor(Pattern::beacon),
+    ),
+    entry(
+        "Pulsar",
+        Oscillator,
+        Some(3),
+        None,
+        Some("John Conway"),
+        Constructor(Pattern::pulsar),
+    ),
+    entry(
+        "Pentadecathlon",
+        Oscillator,
+        Some(15),
+        None,
+        Some("John Conway"),
+        Constructor(Pattern::penta_decathlon),
+    ),
+    entry(
+        "Glider",
+        Spaceship,
+        Some(4),
+        Some((1, 4)),
+        Some("Richard K. Guy"),
+        Constructor(Pattern::glider),
+    ),
+    entry(
+        "Lightweight spaceship",
+        Spaceship,
+        Some(4),
+        Some((1, 2)),
+        Some("John Conway"),
+        Constructor(Pattern::lwss),
+    ),
+    entry(
+        "Middleweight spaceship",
+        Spaceship,
+        Some(4),
+        Some((1, 2)),
+        Some("John Conway"),
+        Constructor(Pattern::mwss),
+    ),
+    entry(
+        "Heavyweight spaceship",
+        Spaceship,
+        Some(4),
+        Some((1, 2)),
+        Some("John Conway"),
+        Constructor(Pattern::hwss),
+    ),
+    entry(
+        "R-pentomino",
+        Methuselah,
+        None,
+        None,
+        Some("John Conway"),
+        Rle("x = 3, y = 3\nb2o$2o$bo!"),
+    ),
+    entry(
+        "Acorn",
+        Methuselah,
+        None,
+        None,
+        Some("Charles Corderman"),
+        Rle("x = 7, y = 3\nbo$3bo$2o2b3o!"),
+    ),
+    entry(
+        "Diehard",
+        Methuselah,
+        None,
+        None,
+        None,
+        Rle("x = 8, y = 3\n6bo$2o$bo3b3o!"),
+    ),
+    entry(
+        "Gosper glider gun",
+        Gun,
+        Some(30),
+        None,
+        Some("Bill Gosper"),
+        Rle(
+            "x = 36, y = 9\n24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$\
+             2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!",
+        ),
+    ),
+    entry(
+        "Simkin glider gun",
+        Gun,
+        Some(120),
+        None,
+        Some("Michael Simkin"),
+        Rle(
+            "x = 33, y = 21\n2o5b2o$2o5b2o2$4b2o$4b2o5$22b2ob2o$21bo5bo$21bo6bo2b2o$\
+             21b3o3bo3b2o$26bo4$20b2o$20bo$21b3o$23bo!",
+        ),
+    ),
+    entry(
+        "Blinker puffer 1",
+        Puffer,
+        Some(8),
+        Some((1, 2)),
+        Some("Robert Wainwright"),
+        Rle(
+            "x = 9, y = 18\n3bo$bo3bo$o$o4bo$5o4$b2o$2ob3o$b4o$2b2o2$5b2o$3bo4bo$2bo$\
+             2bo5bo$2b6o!",
+        ),
+    ),
+    entry(
+        "Eater 1",
+        Eater,
+        Some(1),
+        None,
+        Some("Bill Gosper"),
+        Rle("x = 4, y = 4\n2o$obo$2bo$2b2o!"),
+    ),
+    /*The glider below the pentadecathlon reaches it in the right phase to be sent back
+    the way it came.*/
+    entry(
+        "Pentadecathlon reflector",
+        Reflector,
+        Some(15),
+        None,
+        None,
+        Rle("x = 15, y = 31\nbo$bo$obo$bo$bo$bo$bo$obo$bo$bo19$12b3o$12bo$13bo!"),
+    ),
+];
+
+pub fn entries() -> &'static [Entry] {
+    &ENTRIES
+}
+
+/*Names match ignoring case, spaces, hyphens and underscores, so "gosper_glider_gun" finds
+"Gosper glider gun".*/
+pub fn find(name: &str) -> Option<&'static Entry> {
+    let key = |s: &str| -> String {
+        s.chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let name = key(name);
+    ENTRIES.iter().find(|entry| key(entry.name) == name)
+}
+
+pub fn by_category(category: Category) -> impl Iterator<Item = &'static Entry> {
+    ENTRIES
+        .iter()
+        .filter(move |entry| entry.category == category)
+}
+
+/*One line per entry, grouped by category, for frontends to print.*/
+pub fn write_list<W: Write>(mut writer: W) -> io::Result<()> {
+    for category in Category::ALL {
+        for entry in by_category(category) {
+            let period = entry
+                .period
+                .map_or(String::from("-"), |p| format!("p{}", p));
+            let speed = entry.speed.map_or(String::from("-"), |s| s.to_string());
+            let (width, height) = entry.pattern().size();
+            writeln!(
+                writer,
+                "{:<24} {:<11} {:<5} {:<5} {:>3}x{:<3} {}",
+                entry.name,
+                category,
+                period,
+                speed,
+                width,
+                height,
+                entry.discoverer.unwrap_or("-"),
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use crate::{
+    next_cell_state_edge, next_cell_state_interior, Grid, GridPoint, GridStorage, GridUnit, Rule,
+    Topology,
+};
+use std::{fmt, mem};
+
+const DEFAULT_TILE_SIZE: GridUnit = 32;
+
+/*Grid split into square tiles, each remembering whether any of its cells changed in the
+last generation. A tile is only recomputed when it or one of its neighbouring tiles
+changed; otherwise it has settled and is left asleep. While a tile sleeps both buffers
+hold the same cells for it, so skipping it needs no copying.*/
+#[derive(Clone)]
+pub struct GridChunked {
+    size: GridPoint,
+    tile_size: GridUnit,
+    tiles: (usize, usize),
+    current_vec: Vec<bool>,
+    next_vec: Vec<bool>,
+    changed: Vec<bool>,
+    rule: Rule,
+    topology: Topology,
+    generation: u64,
+}
+
+impl GridChunked {
+    pub fn empty(size: GridPoint) -> Self {
+        Self::with_tile_size(size, DEFAULT_TILE_SIZE)
+    }
+
+    pub fn with_tile_size(size: GridPoint, tile_size: GridUnit) -> Self {
+        let tile_size = tile_size.max(1);
+        let tiles = (
+            (size.0 as usize).div_ceil(tile_size as usize),
+            (size.1 as usize).div_ceil(tile_size as usize),
+        );
+        let next_vec = vec![false; size.0 as usize * size.1 as usize];
+        Self {
+            size,
+            tile_size,
+            tiles,
+            current_vec: next_vec.clone(),
+            next_vec,
+            changed: vec![true; tiles.0 * tiles.1],
+            rule: Rule::default(),
+            topology: Topology::default(),
+            generation: 0,
+        }
+    }
+
+    pub fn tile_size(&self) -> GridUnit {
+        self.tile_size
+    }
+
+    /*Number of tiles that the next update will recompute.*/
+    pub fn awake_tile_count(&self) -> usize {
+        self.awake_tiles().iter().filter(|&&awake| awake).count()
+    }
+
+    fn get_index(&self, point: GridPoint) -> usize {
+        point.0 as usize + point.1 as usize * self.size.0 as usize
+    }
+
+    fn get_tile_index(&self, (x, y): GridPoint) -> usize {
+        x as usize / self.tile_size as usize + y as usize / self.tile_size as usize * self.tiles.0
+    }
+
+    fn is_border_tile(&self, (tile_x, tile_y): (usize, usize)) -> bool {
+        tile_x == 0 || tile_y == 0 || tile_x == self.tiles.0 - 1 || tile_y == self.tiles.1 - 1
+    }
+
+    fn wake_all(&mut self) {
+        self.changed.iter_mut().for_each(|changed| *changed = true);
+    }
+
+    /*Unless the grid is a plane, a border tile neighbours tiles on the opposite border,
+    possibly mirrored, so any change along the border wakes the whole border.*/
+    fn awake_tiles(&self) -> Vec<bool> {
+        let (width, height) = self.tiles;
+        let tile_points = || (0..height).flat_map(|y| (0..width).map(move |x| (x, y)));
+        let border_changed = self.topology != Topology::Plane
+            && tile_points()
+                .any(|(x, y)| self.is_border_tile((x, y)) && self.changed[y * width + x]);
+
+        tile_points()
+            .map(|(x, y)| {
+                (border_changed && self.is_border_tile((x, y)))
+                    || (y.saturating_sub(1)..=(y + 1).min(height - 1)).any(|y| {
+                        (x.saturating_sub(1)..=(x + 1).min(width - 1))
+                            .any(|x| self.changed[y * width + x])
+                    })
+            })
+            .collect()
+    }
+
+    fn set_next_state_chunked(&mut self) {
+        let awake = self.awake_tiles();
+        let rule = self.rule;
+        let x_max = self.size.0 - 1;
+        let y_max = self.size.1 - 1;
+        let tile_size = self.tile_size as usize;
+        let mut changed = vec![false; awake.len()];
+
+        for (tile, _) in awake.iter().enumerate().filter(|(_, &awake)| awake) {
+            let x_start = (tile % self.tiles.0 * tile_size) as GridUnit;
+            let y_start = (tile / self.tiles.0 * tile_size) as GridUnit;
+            let x_end = (x_start as usize + tile_size).min(self.size.0 as usize) as GridUnit;
+            let y_end = (y_start as usize + tile_size).min(self.size.1 as usize) as GridUnit;
+
+            for y in y_start..y_end {
+                for x in x_start..x_end {
+                    let next = if x == 0 || y == 0 || x == x_max || y == y_max {
+                        next_cell_state_edge(&*self, (x, y))
+                    } else {
+                        next_cell_state_interior(&*self, &rule, (x, y))
+                    };
+                    let index = self.get_index((x, y));
+                    changed[tile] |= next != self.current_vec[index];
+                    self.next_vec[index] = next;
+                }
+            }
+        }
+        self.changed = changed;
+    }
+}
+
+impl GridStorage for GridChunked {
+    fn _size(&self) -> GridPoint {
+        self.size
+    }
+
+    fn _rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    fn _rule_mut(&mut self) -> &mut Rule {
+        /*Settled tiles may not stay settled under a new rule.*/
+        self.wake_all();
+        &mut self.rule
+    }
+
+    fn _topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    fn _topology_mut(&mut self) -> &mut Topology {
+        self.wake_all();
+        &mut self.topology
+    }
+
+    fn _generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn _generation_mut(&mut self) -> &mut u64 {
+        &mut self.generation
+    }
+
+    fn _get_cell_unchecked(&self, point: GridPoint) -> bool {
+        self.current_vec[self.get_index(point)]
+    }
+
+    fn _set_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        let index = self.get_index(point);
+        self.current_vec[index] = b;
+        let tile = self.get_tile_index(point);
+        self.changed[tile] = true;
+    }
+
+    fn _set_next_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        let index = self.get_index(point);
+        self.next_vec[index] = b;
+    }
+
+    /*Only used by generic update paths, which write every cell without tracking changes,
+    so every tile is recomputed next time.*/
+    fn _swap_buffers(&mut self) {
+        mem::swap(&mut self.current_vec, &mut self.next_vec);
+        self.wake_all();
+    }
+
+    fn _take_next_cells(&mut self) -> Option<Vec<bool>> {
+        Some(mem::take(&mut self.next_vec))
+    }
+
+    fn _restore_next_cells(&mut self, cells: Vec<bool>) {
+        self.next_vec = cells;
+    }
+
+    fn _box_clone(&self) -> Box<dyn Grid> {
+        Box::new(self.clone())
+    }
+
+    fn _g_fmt(&self, tc: char, fc: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, b) in self.current_vec.iter().enumerate() {
+            write!(f, "{}", if *b { tc } else { fc })?;
+            if (i + 1) % self.size.0 as usize == 0 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Grid for GridChunked {
+    fn update(&mut self) {
+        self.set_next_state_chunked();
+        mem::swap(&mut self.current_vec, &mut self.next_vec);
+        self.generation += 1;
+    }
+}
+
+impl fmt::Display for GridChunked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self._g_fmt('\u{2588}', ' ', f)
+    }
+}
+
+impl fmt::Debug for GridChunked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self._g_fmt('X', '-', f)
+    }
+}
@@ -1,5 +1,18 @@
 use crate::GridPoint;
-use sdl2::pixels::Color;
+
+/*Colours are plain RGB triples, for frontends to convert to whatever they draw with.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
 
 #[derive(Clone, Copy)]
 pub enum Rgb {
@@ -11,9 +24,9 @@ pub enum Rgb {
 impl From<Rgb> for Color {
     fn from(rgb: Rgb) -> Self {
         match rgb {
-            Rgb::Red => Self::RGB(255, 0, 0),
-            Rgb::Green => Self::RGB(0, 255, 0),
-            Rgb::Blue => Self::RGB(0, 0, 255),
+            Rgb::Red => Self::rgb(255, 0, 0),
+            Rgb::Green => Self::rgb(0, 255, 0),
+            Rgb::Blue => Self::rgb(0, 0, 255),
         }
     }
 }
@@ -31,12 +44,12 @@ pub enum Rygcbm {
 impl From<Rygcbm> for Color {
     fn from(rygcbm: Rygcbm) -> Self {
         match rygcbm {
-            Rygcbm::Red => Self::RGB(255, 0, 0),
-            Rygcbm::Yellow => Self::RGB(255, 255, 0),
-            Rygcbm::Green => Self::RGB(0, 255, 0),
-            Rygcbm::Cyan => Self::RGB(0, 255, 255),
-            Rygcbm::Blue => Self::RGB(0, 0, 255),
-            Rygcbm::Magenta => Self::RGB(255, 0, 255),
+            Rygcbm::Red => Self::rgb(255, 0, 0),
+            Rygcbm::Yellow => Self::rgb(255, 255, 0),
+            Rygcbm::Green => Self::rgb(0, 255, 0),
+            Rygcbm::Cyan => Self::rgb(0, 255, 255),
+            Rygcbm::Blue => Self::rgb(0, 0, 255),
+            Rygcbm::Magenta => Self::rgb(255, 0, 255),
         }
     }
 }
@@ -79,7 +92,7 @@ impl CellStates {
         }
         cell_state.color
     }
-    
+
     fn reset(&mut self, color: Color) {
         for row in &mut self.cells {
             for cell_state in row {
@@ -134,22 +147,22 @@ impl CyclicalModulator {
                 if self.color_state.r == u8::MAX {
                     if self.color_state.b > 0 {
                         self.color_state.b -= 1;
-                    } else if self.color_state.g < u8::MAX {
-                        self.color_state.g += 1;
+                    } else {
+                        self.color_state.g = self.color_state.g.saturating_add(1);
                     }
                 }
                 if self.color_state.g == u8::MAX {
                     if self.color_state.r > 0 {
                         self.color_state.r -= 1;
-                    } else if self.color_state.b < u8::MAX {
-                        self.color_state.b += 1;
+                    } else {
+                        self.color_state.b = self.color_state.b.saturating_add(1);
                     }
                 }
                 if self.color_state.b == u8::MAX {
                     if self.color_state.g > 0 {
                         self.color_state.g -= 1;
-                    } else if self.color_state.r < u8::MAX {
-                        self.color_state.r += 1;
+                    } else {
+                        self.color_state.r = self.color_state.r.saturating_add(1);
                     }
                 }
             }
@@ -183,7 +196,8 @@ impl NewCellColorCyclical {
     }
 
     pub fn get_cell_color(&mut self, point: GridPoint, cell: bool) -> Color {
-	self.cell_states.get_cell_color(point, cell, self.cyclical_modulator.color())
+        self.cell_states
+            .get_cell_color(point, cell, self.cyclical_modulator.color())
     }
 
     pub fn update(&mut self) {
@@ -212,45 +226,22 @@ impl NewCellColorHeatMap {
     }
 
     pub fn get_cell_color(&mut self, point: GridPoint, cell: bool) -> Color {
-	self.cell_states.get_cell_color(point, cell, self.hot.into())
+        self.cell_states
+            .get_cell_color(point, cell, self.hot.into())
     }
 
     pub fn update(&mut self) {
         for row in &mut self.cell_states.cells {
             for cell_state in row {
                 match self.hot {
-                    Rgb::Red => {
-                        if cell_state.color.r > 0 {
-                            cell_state.color.r -= 1;
-                        }
-                    }
-                    Rgb::Green => {
-                        if cell_state.color.g > 0 {
-                            cell_state.color.g -= 1;
-                        }
-                    }
-                    Rgb::Blue => {
-                        if cell_state.color.b > 0 {
-                            cell_state.color.b -= 1;
-                        }
-                    }
+                    Rgb::Red => cell_state.color.r = cell_state.color.r.saturating_sub(1),
+                    Rgb::Green => cell_state.color.g = cell_state.color.g.saturating_sub(1),
+                    Rgb::Blue => cell_state.color.b = cell_state.color.b.saturating_sub(1),
                 }
                 match self.cold {
-                    Rgb::Red => {
-                        if cell_state.color.r < u8::MAX {
-                            cell_state.color.r += 1;
-                        }
-                    }
-                    Rgb::Green => {
-                        if cell_state.color.g < u8::MAX {
-                            cell_state.color.g += 1;
-                        }
-                    }
-                    Rgb::Blue => {
-                        if cell_state.color.b < u8::MAX {
-                            cell_state.color.b += 1;
-                        }
-                    }
+                    Rgb::Red => cell_state.color.r = cell_state.color.r.saturating_add(1),
+                    Rgb::Green => cell_state.color.g = cell_state.color.g.saturating_add(1),
+                    Rgb::Blue => cell_state.color.b = cell_state.color.b.saturating_add(1),
                 }
             }
         }
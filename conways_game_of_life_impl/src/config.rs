@@ -1,119 +1,63 @@
-use crate::{BResult, Grid, GridPoint};
+use crate::{BResult, Grid, GridPoint, Pattern, StampMode};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
-pub fn block<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_hline(x, 2, y, true)?;
-    grid.set_hline(x, 2, y + 1, true)
+pub fn block<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::block(), point, StampMode::Or)
 }
 
-pub fn bee_hive<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_hline(x + 1, 2, y, true)?;
-    grid.set_cell((x, y + 1), true)?;
-    grid.set_cell((x + 3, y + 1), true)?;
-    grid.set_hline(x + 1, 2, y + 2, true)
+pub fn bee_hive<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::bee_hive(), point, StampMode::Or)
 }
 
-pub fn loaf<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_hline(x + 1, 2, y, true)?;
-    grid.set_vline(y + 1, 2, x + 3, true)?;
-    grid.set_cell((x, y + 1), true)?;
-    grid.set_cell((x + 1, y + 2), true)?;
-    grid.set_cell((x + 2, y + 3), true)
+pub fn loaf<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::loaf(), point, StampMode::Or)
 }
 
-pub fn boat<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_hline(x, 2, y, true)?;
-    grid.set_cell((x, y + 1), true)?;
-    grid.set_cell((x + 2, y + 1), true)?;
-    grid.set_cell((x + 1, y + 2), true)
+pub fn boat<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::boat(), point, StampMode::Or)
 }
 
-pub fn tub<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_cell((x + 1, y), true)?;
-    grid.set_cell((x, y + 1), true)?;
-    grid.set_cell((x + 2, y + 1), true)?;
-    grid.set_cell((x + 1, y + 2), true)
+pub fn tub<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::tub(), point, StampMode::Or)
 }
 
-pub fn blinker<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_hline(x, 3, y, true)
+pub fn blinker<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::blinker(), point, StampMode::Or)
 }
 
-pub fn toad<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_hline(x + 1, 3, y, true)?;
-    grid.set_hline(x, 3, y + 1, true)
+pub fn toad<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::toad(), point, StampMode::Or)
 }
 
-pub fn beacon<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    block(grid, (x, y))?;
-    block(grid, (x + 2, y + 2))
+pub fn beacon<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::beacon(), point, StampMode::Or)
 }
 
-pub fn pulsar<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    let length = 3;
-
-    grid.set_hline(x + 2, length, y, true)?;
-    grid.set_hline(x + 2, length, y + 5, true)?;
-    grid.set_hline(x + 2, length, y + 7, true)?;
-    grid.set_hline(x + 2, length, y + 12, true)?;
-
-    grid.set_hline(x + 8, length, y, true)?;
-    grid.set_hline(x + 8, length, y + 5, true)?;
-    grid.set_hline(x + 8, length, y + 7, true)?;
-    grid.set_hline(x + 8, length, y + 12, true)?;
-
-    grid.set_vline(y + 2, length, x, true)?;
-    grid.set_vline(y + 2, length, x + 5, true)?;
-    grid.set_vline(y + 2, length, x + 7, true)?;
-    grid.set_vline(y + 2, length, x + 12, true)?;
-
-    grid.set_vline(y + 8, length, x, true)?;
-    grid.set_vline(y + 8, length, x + 5, true)?;
-    grid.set_vline(y + 8, length, x + 7, true)?;
-    grid.set_vline(y + 8, length, x + 12, true)
+pub fn pulsar<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::pulsar(), point, StampMode::Or)
 }
 
-pub fn penta_decathlon<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_vline(y, 10, x + 1, true)?;
-    grid.set_hline(x, 3, y + 2, true)?;
-    grid.set_hline(x, 3, y + 7, true)?;
-
-    grid.set_cell((x + 1, y + 2), false)?;
-    grid.set_cell((x + 1, y + 7), false)
+pub fn penta_decathlon<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::penta_decathlon(), point, StampMode::Or)
 }
 
-pub fn glider<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_cell((x + 1, y), true)?;
-    grid.set_cell((x + 2, y + 1), true)?;
-    grid.set_hline(x, 3, y + 2, true)
+pub fn glider<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::glider(), point, StampMode::Or)
 }
 
-pub fn lwss<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_cell((x + 1, y), true)?;
-    grid.set_cell((x + 4, y), true)?;
-    grid.set_cell((x + 4, y + 2), true)?;
-    grid.set_vline(y + 1, 2, x, true)?;
-    grid.set_hline(x, 4, y + 3, true)
+pub fn lwss<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::lwss(), point, StampMode::Or)
 }
 
-pub fn mwss<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_cell((x + 3, y), true)?;
-    grid.set_cell((x + 1, y + 1), true)?;
-    grid.set_cell((x + 5, y + 1), true)?;
-    grid.set_cell((x + 5, y + 3), true)?;
-    grid.set_vline(y + 2, 2, x, true)?;
-    grid.set_hline(x, 5, y + 4, true)
+pub fn mwss<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::mwss(), point, StampMode::Or)
 }
 
-pub fn hwss<G: Grid>(grid: &mut G, (x, y): GridPoint) -> BResult<()> {
-    grid.set_hline(x + 3, 2, y, true)?;
-    grid.set_cell((x + 1, y + 1), true)?;
-    grid.set_cell((x + 6, y + 1), true)?;
-    grid.set_cell((x + 6, y + 3), true)?;
-    grid.set_vline(y + 2, 2, x, true)?;
-    grid.set_hline(x, 6, y + 4, true)
+pub fn hwss<G: Grid>(grid: &mut G, point: GridPoint) -> BResult<()> {
+    grid.stamp(&Pattern::hwss(), point, StampMode::Or)
 }
 
+/*Always seeded with 0, so benchmarks see the same soup every run. Soup takes any seed.*/
 pub fn random<G: Grid>(grid: &mut G, mut density: f64) -> BResult<()> {
     density = density.clamp(0., 1.);
     let mut rng = <StdRng as SeedableRng>::seed_from_u64(0);
@@ -0,0 +1,357 @@
+use crate::{check_region, color::Color, Grid, GridPoint, OutOfBounds};
+use flate2::{write::ZlibEncoder, Compression, Crc};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::{self, Write},
+};
+
+const BACKGROUND: u8 = 0;
+const CELL: u8 = 1;
+const LINE: u8 = 2;
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    OutOfBounds(OutOfBounds),
+    /*Width and height in pixels, too large for the format.*/
+    TooLarge(u64, u64),
+    /*The image would have no pixels, which the formats do not allow.*/
+    Empty,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<OutOfBounds> for ExportError {
+    fn from(e: OutOfBounds) -> Self {
+        Self::OutOfBounds(e)
+    }
+}
+
+/*A rendered frame: one palette index per pixel, row by row.*/
+/*Binary PPM (P6) of pixels already drawn, three bytes of red, green and blue each, row by
+row from the top left. Fails without writing anything if rgb does not hold exactly width by
+height pixels.*/
+pub fn write_ppm<W: Write>(
+    (width, height): (usize, usize),
+    rgb: &[u8],
+    mut writer: W,
+) -> io::Result<()> {
+    if Some(rgb.len())
+        != width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} bytes of rgb for {}x{} pixels", rgb.len(), width, height),
+        ));
+    }
+    write!(writer, "P6\n{} {}\n255\n", width, height)?;
+    writer.write_all(rgb)
+}
+
+struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+/*Writes grids as images, each cell a square of scale pixels. Grid lines are one pixel
+wide and drawn around every cell. The default colours are those of the SDL renderer: a
+black grid background and light grey cells. Without a region the whole grid is drawn.*/
+#[derive(Clone, Debug)]
+pub struct Exporter {
+    scale: u32,
+    grid_lines: Option<Color>,
+    background_color: Color,
+    cell_color: Color,
+    region: Option<(GridPoint, GridPoint)>,
+}
+
+impl Exporter {
+    pub fn new() -> Self {
+        Self {
+            scale: 1,
+            grid_lines: None,
+            background_color: Color::rgb(0, 0, 0),
+            cell_color: Color::rgb(200, 200, 200),
+            region: None,
+        }
+    }
+
+    /*A scale of 0 is taken as 1.*/
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    pub fn grid_lines(mut self, color: Option<Color>) -> Self {
+        self.grid_lines = color;
+        self
+    }
+
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    pub fn cell_color(mut self, color: Color) -> Self {
+        self.cell_color = color;
+        self
+    }
+
+    pub fn region(mut self, top_left: GridPoint, size: GridPoint) -> Self {
+        self.region = Some((top_left, size));
+        self
+    }
+
+    fn palette(&self) -> [Color; 4] {
+        [
+            self.background_color,
+            self.cell_color,
+            self.grid_lines.unwrap_or(self.background_color),
+            self.background_color,
+        ]
+    }
+
+    fn image_size<G: Grid>(&self, grid: &G) -> Result<(u64, u64), ExportError> {
+        let ((x, y), (width, height)) = self.region.unwrap_or(((0, 0), grid.size()));
+        check_region(grid, (x, y), (width, height))?;
+        let length = |cells: u16| {
+            let mut length = cells as u64 * self.scale as u64;
+            if self.grid_lines.is_some() {
+                length += cells as u64 + 1;
+            }
+            length
+        };
+        match (length(width), length(height)) {
+            (0, _) | (_, 0) => Err(ExportError::Empty),
+            size => Ok(size),
+        }
+    }
+
+    fn render<G: Grid>(&self, grid: &G, (width, height): (u64, u64)) -> Frame {
+        let ((x, y), _) = self.region.unwrap_or(((0, 0), grid.size()));
+        let (width, height) = (width as usize, height as usize);
+        let scale = self.scale as usize;
+        let lines = self.grid_lines.is_some();
+        let pitch = scale + lines as usize;
+        /*Which cell a pixel coordinate falls in, or None for a grid line.*/
+        let cell = |pixel: usize| {
+            if lines && pixel.is_multiple_of(pitch) {
+                None
+            } else {
+                Some((pixel / pitch) as u16)
+            }
+        };
+
+        let mut pixels = Vec::with_capacity(width * height);
+        let mut row = vec![BACKGROUND; width];
+        let mut last_cell_row = None;
+        for pixel_y in 0..height {
+            let cell_y = cell(pixel_y);
+            if pixel_y == 0 || cell_y != last_cell_row {
+                for (pixel_x, pixel) in row.iter_mut().enumerate() {
+                    *pixel = match (cell(pixel_x), cell_y) {
+                        (Some(cell_x), Some(cell_y)) => {
+                            if grid.get_cell_unchecked((x + cell_x, y + cell_y)) {
+                                CELL
+                            } else {
+                                BACKGROUND
+                            }
+                        }
+                        _ => LINE,
+                    };
+                }
+                last_cell_row = cell_y;
+            }
+            pixels.extend_from_slice(&row);
+        }
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /*Binary PPM (P6).*/
+    pub fn write_ppm<G: Grid, W: Write>(&self, grid: &G, writer: W) -> Result<(), ExportError> {
+        let frame = self.render(grid, self.image_size(grid)?);
+        let palette = self.palette();
+        let mut rgb = Vec::with_capacity(frame.pixels.len() * 3);
+        for &index in &frame.pixels {
+            let color = palette[index as usize];
+            rgb.extend_from_slice(&[color.r, color.g, color.b]);
+        }
+        Ok(write_ppm((frame.width, frame.height), &rgb, writer)?)
+    }
+
+    /*Indexed colour PNG, 8 bits per pixel.*/
+    pub fn write_png<G: Grid, W: Write>(&self, grid: &G, mut writer: W) -> Result<(), ExportError> {
+        let size = self.image_size(grid)?;
+        if size.0 > u32::MAX as u64 || size.1 > u32::MAX as u64 {
+            return Err(ExportError::TooLarge(size.0, size.1));
+        }
+        let frame = self.render(grid, size);
+
+        fn chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+            let mut crc = Crc::new();
+            crc.update(kind);
+            crc.update(data);
+            writer.write_all(&(data.len() as u32).to_be_bytes())?;
+            writer.write_all(kind)?;
+            writer.write_all(data)?;
+            writer.write_all(&crc.sum().to_be_bytes())
+        }
+
+        writer.write_all(b"\x89PNG\r\n\x1a\n")?;
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(frame.width as u32).to_be_bytes());
+        header.extend_from_slice(&(frame.height as u32).to_be_bytes());
+        /*Bit depth 8, colour type 3 (indexed), then default compression, filter and no
+        interlacing.*/
+        header.extend_from_slice(&[8, 3, 0, 0, 0]);
+        chunk(&mut writer, b"IHDR", &header)?;
+        let palette: Vec<u8> = self.palette()[..3]
+            .iter()
+            .flat_map(|color| [color.r, color.g, color.b])
+            .collect();
+        chunk(&mut writer, b"PLTE", &palette)?;
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        for row in frame.pixels.chunks(frame.width.max(1)) {
+            encoder.write_all(&[0])?;
+            encoder.write_all(row)?;
+        }
+        chunk(&mut writer, b"IDAT", &encoder.finish()?)?;
+        chunk(&mut writer, b"IEND", &[])?;
+        Ok(())
+    }
+
+    /*Animated GIF of the grid's current generation followed by the next generations,
+    each shown for delay hundredths of a second and looping forever. Updates the grid.*/
+    pub fn write_gif<G: Grid, W: Write>(
+        &self,
+        grid: &mut G,
+        generations: u64,
+        delay: u16,
+        mut writer: W,
+    ) -> Result<(), ExportError> {
+        let size = self.image_size(grid)?;
+        if size.0 > u16::MAX as u64 || size.1 > u16::MAX as u64 {
+            return Err(ExportError::TooLarge(size.0, size.1));
+        }
+        let (width, height) = (size.0 as u16, size.1 as u16);
+
+        writer.write_all(b"GIF89a")?;
+        writer.write_all(&width.to_le_bytes())?;
+        writer.write_all(&height.to_le_bytes())?;
+        /*Global colour table of 4 entries, background colour 0, square pixels.*/
+        writer.write_all(&[0xf1, BACKGROUND, 0])?;
+        for color in self.palette() {
+            writer.write_all(&[color.r, color.g, color.b])?;
+        }
+        /*Loop forever.*/
+        writer.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        for generation in 0..=generations {
+            if generation != 0 {
+                grid.update();
+            }
+            let frame = self.render(grid, size);
+            writer.write_all(&[0x21, 0xf9, 0x04, 0x00])?;
+            writer.write_all(&delay.to_le_bytes())?;
+            writer.write_all(&[0, 0])?;
+            writer.write_all(&[0x2c, 0, 0, 0, 0])?;
+            writer.write_all(&width.to_le_bytes())?;
+            writer.write_all(&height.to_le_bytes())?;
+            writer.write_all(&[0])?;
+            writer.write_all(&[2])?;
+            for block in lzw(2, &frame.pixels).chunks(255) {
+                writer.write_all(&[block.len() as u8])?;
+                writer.write_all(block)?;
+            }
+            writer.write_all(&[0])?;
+        }
+        writer.write_all(&[0x3b])?;
+        Ok(())
+    }
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*Variable width LZW as GIF uses it, with codes packed least significant bit first. The
+code width grows once the next free code no longer fits, and the table is cleared once
+it holds 4096 codes.*/
+fn lzw(min_code_size: u8, indices: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut code_size = min_code_size as u32 + 1;
+    let mut next = end + 1;
+    let mut table = HashMap::new();
+
+    let mut emit = |code: u16, code_size: u32| {
+        buffer |= (code as u32) << bits;
+        bits += code_size;
+        while bits >= 8 {
+            out.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    };
+
+    emit(clear, code_size);
+    let mut indices = indices.iter();
+    if let Some(&first) = indices.next() {
+        let mut prefix = first as u16;
+        for &index in indices {
+            if let Some(&code) = table.get(&(prefix, index)) {
+                prefix = code;
+                continue;
+            }
+            emit(prefix, code_size);
+            if next < 4096 {
+                table.insert((prefix, index), next);
+                /*The decoder adds its entry one code later, so the width grows once
+                the code just added no longer fits.*/
+                if next == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+                next += 1;
+            } else {
+                emit(clear, code_size);
+                table.clear();
+                code_size = min_code_size as u32 + 1;
+                next = end + 1;
+            }
+            prefix = index as u16;
+        }
+        emit(prefix, code_size);
+    }
+    emit(end, code_size);
+    if bits > 0 {
+        out.push(buffer as u8);
+    }
+    out
+}
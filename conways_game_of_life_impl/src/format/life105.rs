@@ -0,0 +1,150 @@
+use super::{
+    comment_texts, parse_point, read_comment_text, read_with, rows, set_cells_from_points,
+    ParseError, ParseErrorKind, PatternFile, ReadError,
+};
+use crate::{Grid, GridPoint, GridUnit, Rule};
+
+const HEADER: &str = "#Life 1.05";
+const BLOCK_WIDTH: usize = 80;
+
+pub fn read<G: Grid>(grid: &mut G, s: &str, top_left: GridPoint) -> Result<PatternFile, ReadError> {
+    read_with(grid, s, top_left, parse)
+}
+
+/*Cells come in blocks of '.' and '*' rows, each starting at the signed offset given by
+the #P line before it. #N selects Conway's rule and #R gives a survival/birth rule.*/
+pub fn parse(s: &str) -> Result<PatternFile, ParseError> {
+    let mut pattern = PatternFile::default();
+    let mut lines = s
+        .lines()
+        .zip(1..)
+        .skip_while(|(line, _)| line.trim().is_empty());
+    match lines.next() {
+        Some((line, _)) if line.trim() == HEADER => (),
+        Some((_, line_number)) => {
+            return Err(ParseError::new(
+                line_number,
+                1,
+                ParseErrorKind::MissingHeader,
+            ))
+        }
+        None => return Err(ParseError::new(1, 1, ParseErrorKind::MissingHeader)),
+    }
+
+    let mut points = Vec::new();
+    let mut block = (0, 0);
+    let mut y = 0;
+    for (line, line_number) in lines {
+        let line = line.trim_end();
+        if let Some(rest) = line.strip_prefix('#') {
+            let mut chars = rest.chars();
+            match chars.next() {
+                Some('D') | Some('C') => read_comment_text(&mut pattern, chars.as_str()),
+                Some('N') => pattern.rule = Some(Rule::CONWAY),
+                Some('R') => {
+                    pattern.rule = Some(chars.as_str().trim().parse().map_err(|e| {
+                        ParseError::new(line_number, 3, ParseErrorKind::InvalidRule(e))
+                    })?)
+                }
+                Some('P') => {
+                    block = parse_point(line, line_number, 1)?;
+                    y = 0;
+                }
+                _ => (),
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        for (c, x) in line.chars().zip(0..) {
+            let error = |kind| ParseError::new(line_number, x as usize + 1, kind);
+            match c {
+                '.' => (),
+                '*' | 'O' => points.push(
+                    block
+                        .0
+                        .checked_add(x)
+                        .zip(block.1.checked_add(y))
+                        .ok_or_else(|| error(ParseErrorKind::PatternTooLarge))?,
+                ),
+                c => return Err(error(ParseErrorKind::UnexpectedCharacter(c))),
+            }
+        }
+        y += 1;
+    }
+
+    set_cells_from_points(&mut pattern, &points).map_err(|kind| ParseError::new(1, 1, kind))?;
+    Ok(pattern)
+}
+
+/*Lines are limited to 80 cells, so wider patterns are split into column bands. Each run
+of consecutive non-empty rows within a band is written as its own #P block.*/
+pub fn write(pattern: &PatternFile) -> String {
+    let mut ret = String::from(HEADER);
+    ret.push('\n');
+    for text in comment_texts(pattern) {
+        ret.push_str(&format!("#D {}\n", text));
+    }
+    match pattern.rule {
+        Some(Rule::CONWAY) | None => ret.push_str("#N\n"),
+        Some(rule) => {
+            let counts = |f: &dyn Fn(u8) -> bool| {
+                (0..=8)
+                    .filter(|&n| f(n))
+                    .map(|n| n.to_string())
+                    .collect::<String>()
+            };
+            ret.push_str(&format!(
+                "#R {}/{}\n",
+                counts(&|n| rule.survival(n)),
+                counts(&|n| rule.birth(n))
+            ));
+        }
+    }
+
+    let rows = rows(pattern);
+    for band_start in (0..pattern.size.0).step_by(BLOCK_WIDTH) {
+        let band = band_start as usize..band_start as usize + BLOCK_WIDTH;
+        let band_rows: Vec<Vec<GridUnit>> = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .copied()
+                    .filter(|&x| band.contains(&(x as usize)))
+                    .collect()
+            })
+            .collect();
+
+        let mut y = 0;
+        while y < band_rows.len() {
+            if band_rows[y].is_empty() {
+                y += 1;
+                continue;
+            }
+            let block_len = band_rows[y..]
+                .iter()
+                .position(Vec::is_empty)
+                .unwrap_or(band_rows.len() - y);
+            let block = &band_rows[y..y + block_len];
+            let x_min = block
+                .iter()
+                .filter_map(|row| row.first())
+                .min()
+                .copied()
+                .unwrap_or(0);
+
+            ret.push_str(&format!("#P {} {}\n", x_min, y));
+            for row in block {
+                let mut line = vec!['.'; (row[row.len() - 1] - x_min) as usize + 1];
+                for &x in row {
+                    line[(x - x_min) as usize] = '*';
+                }
+                ret.extend(line);
+                ret.push('\n');
+            }
+            y += block_len;
+        }
+    }
+    ret
+}
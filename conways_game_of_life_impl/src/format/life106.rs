@@ -0,0 +1,77 @@
+use super::{
+    parse_point, read_with, set_cells_from_points, ParseError, ParseErrorKind, PatternFile,
+    ReadError,
+};
+use crate::{Grid, GridPoint};
+
+const HEADER: &str = "#Life 1.06";
+
+pub fn read<G: Grid>(grid: &mut G, s: &str, top_left: GridPoint) -> Result<PatternFile, ReadError> {
+    read_with(grid, s, top_left, parse)
+}
+
+/*After the header, every line holds the signed x and y coordinates of one live cell. The
+pattern is moved so that its bounding box starts at the origin.*/
+pub fn parse(s: &str) -> Result<PatternFile, ParseError> {
+    let mut pattern = PatternFile::default();
+    let mut lines = s
+        .lines()
+        .zip(1..)
+        .skip_while(|(line, _)| line.trim().is_empty());
+    match lines.next() {
+        Some((line, _)) if line.trim() == HEADER => (),
+        Some((_, line_number)) => {
+            return Err(ParseError::new(
+                line_number,
+                1,
+                ParseErrorKind::MissingHeader,
+            ))
+        }
+        None => return Err(ParseError::new(1, 1, ParseErrorKind::MissingHeader)),
+    }
+
+    let mut points = Vec::new();
+    for (line, line_number) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.trim().strip_prefix('#') {
+            let mut chars = comment.chars();
+            match chars.next() {
+                Some('N') => pattern.name = Some(chars.as_str().trim().to_string()),
+                Some('O') => pattern.author = Some(chars.as_str().trim().to_string()),
+                Some('C') | Some('D') => pattern.comments.push(chars.as_str().trim().to_string()),
+                _ => (),
+            }
+            continue;
+        }
+
+        points.push(parse_point(line, line_number, 0)?);
+    }
+
+    set_cells_from_points(&mut pattern, &points).map_err(|kind| ParseError::new(1, 1, kind))?;
+    Ok(pattern)
+}
+
+/*Name, author and comments are written as #N, #O and #C lines, which common readers
+skip.*/
+pub fn write(pattern: &PatternFile) -> String {
+    let mut ret = String::from(HEADER);
+    ret.push('\n');
+    if let Some(name) = &pattern.name {
+        ret.push_str(&format!("#N {}\n", name));
+    }
+    if let Some(author) = &pattern.author {
+        ret.push_str(&format!("#O {}\n", author));
+    }
+    for comment in &pattern.comments {
+        ret.push_str(&format!("#C {}\n", comment));
+    }
+    let mut cells = pattern.cells.clone();
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    cells.dedup();
+    for (x, y) in cells {
+        ret.push_str(&format!("{} {}\n", x, y));
+    }
+    ret
+}
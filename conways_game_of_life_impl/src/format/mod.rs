@@ -0,0 +1,315 @@
+pub mod life105;
+pub mod life106;
+pub mod plaintext;
+pub mod rle;
+
+use crate::{
+    check_region, BResult, Grid, GridPoint, GridUnit, OutOfBounds, Pattern, Rule, RuleParseError,
+};
+use std::{convert::TryFrom, error::Error, fmt};
+
+/*A pattern as read from, or to be written to, a pattern file. Cells holds the live cells
+relative to the top left corner of the pattern, sorted by row and then column.*/
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PatternFile {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub comments: Vec<String>,
+    pub rule: Option<Rule>,
+    pub size: GridPoint,
+    pub cells: Vec<GridPoint>,
+}
+
+impl PatternFile {
+    pub fn from_grid<G: Grid>(grid: &G) -> Self {
+        let mut ret = Self {
+            rule: Some(grid.rule()),
+            size: grid.size(),
+            ..Self::default()
+        };
+        grid.inspect(|point, grid| {
+            if grid.get_cell_unchecked(point) {
+                ret.cells.push(point);
+            }
+        });
+        ret
+    }
+
+    pub fn from_grid_region<G: Grid>(
+        grid: &G,
+        (x, y): GridPoint,
+        (width, height): GridPoint,
+    ) -> BResult<Self> {
+        check_region(grid, (x, y), (width, height))?;
+        let mut ret = Self {
+            rule: Some(grid.rule()),
+            size: (width, height),
+            ..Self::default()
+        };
+        for y_offset in 0..height {
+            for x_offset in 0..width {
+                if grid.get_cell_unchecked((x + x_offset, y + y_offset)) {
+                    ret.cells.push((x_offset, y_offset));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    pub fn to_pattern(&self) -> BResult<Pattern> {
+        Pattern::new(self.size, self.cells.clone())
+    }
+
+    /*Overwrites the rectangle of the grid covered by the pattern, with its top left corner
+    at (x, y). Nothing is written unless the whole rectangle fits.*/
+    pub fn place<G: Grid>(&self, grid: &mut G, (x, y): GridPoint) -> BResult<()> {
+        check_region(grid, (x, y), self.size)?;
+        for y_offset in 0..self.size.1 {
+            grid.set_hline(x, self.size.0, y + y_offset, false)?;
+        }
+        for &(x_offset, y_offset) in &self.cells {
+            grid.set_cell_unchecked((x + x_offset, y + y_offset), true);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Format {
+    Rle,
+    Plaintext,
+    Life105,
+    Life106,
+}
+
+impl Format {
+    /*Guesses the format from the first line that is not blank or an RLE style comment.*/
+    pub fn detect(s: &str) -> Option<Self> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+        let first = lines.clone().next()?;
+        if first.starts_with("#Life 1.06") {
+            return Some(Self::Life106);
+        }
+        if first.starts_with("#Life 1.05") {
+            return Some(Self::Life105);
+        }
+        if first.starts_with('!') || first.chars().all(|c| matches!(c, '.' | 'O' | '*')) {
+            return Some(Self::Plaintext);
+        }
+        lines
+            .find(|line| !line.starts_with('#'))
+            .filter(|line| line.starts_with('x') && line.contains('='))
+            .map(|_| Self::Rle)
+    }
+
+    pub fn parse(&self, s: &str) -> Result<PatternFile, ParseError> {
+        match self {
+            Self::Rle => rle::parse(s),
+            Self::Plaintext => plaintext::parse(s),
+            Self::Life105 => life105::parse(s),
+            Self::Life106 => life106::parse(s),
+        }
+    }
+
+    pub fn write(&self, pattern: &PatternFile) -> String {
+        match self {
+            Self::Rle => rle::write(pattern),
+            Self::Plaintext => plaintext::write(pattern),
+            Self::Life105 => life105::write(pattern),
+            Self::Life106 => life106::write(pattern),
+        }
+    }
+}
+
+pub fn parse(s: &str) -> Result<PatternFile, ParseError> {
+    Format::detect(s)
+        .ok_or_else(|| ParseError::new(1, 1, ParseErrorKind::UnknownFormat))?
+        .parse(s)
+}
+
+/*Detects the format, places the pattern with its top left corner at top_left, and
+switches the grid to the pattern's rule if it names one.*/
+pub fn read<G: Grid>(grid: &mut G, s: &str, top_left: GridPoint) -> Result<PatternFile, ReadError> {
+    read_with(grid, s, top_left, parse)
+}
+
+fn read_with<G: Grid>(
+    grid: &mut G,
+    s: &str,
+    top_left: GridPoint,
+    parse: fn(&str) -> Result<PatternFile, ParseError>,
+) -> Result<PatternFile, ReadError> {
+    let pattern = parse(s)?;
+    pattern.place(grid, top_left)?;
+    if let Some(rule) = pattern.rule {
+        grid.set_rule(rule);
+    }
+    Ok(pattern)
+}
+
+/*Reads a line of the form "[prefix] x y" into signed coordinates, where prefix is the
+number of leading words to skip.*/
+fn parse_point(line: &str, line_number: usize, prefix: usize) -> Result<(i64, i64), ParseError> {
+    let end = line.chars().count() + 1;
+    let mut words = line
+        .split(char::is_whitespace)
+        .scan(1, |column, word| {
+            let ret = (*column, word);
+            *column += word.chars().count() + 1;
+            Some(ret)
+        })
+        .filter(|(_, word)| !word.is_empty())
+        .skip(prefix);
+    let mut coordinate = || {
+        let (column, word) = words.next().unwrap_or((end, ""));
+        word.parse()
+            .map_err(|_| ParseError::new(line_number, column, ParseErrorKind::InvalidCoordinate))
+    };
+    let point = (coordinate()?, coordinate()?);
+    match words.next() {
+        Some((column, _)) => Err(ParseError::new(
+            line_number,
+            column,
+            ParseErrorKind::InvalidCoordinate,
+        )),
+        None => Ok(point),
+    }
+}
+
+/*Moves points given in arbitrary signed coordinates so that their bounding box starts at
+the origin, for the formats that list cells by position.*/
+fn set_cells_from_points(
+    pattern: &mut PatternFile,
+    points: &[(i64, i64)],
+) -> Result<(), ParseErrorKind> {
+    let min = points
+        .iter()
+        .fold(None, |min: Option<(i64, i64)>, &(x, y)| {
+            Some(min.map_or((x, y), |min| (min.0.min(x), min.1.min(y))))
+        })
+        .unwrap_or((0, 0));
+    let to_unit = |n: i64, min: i64| {
+        n.checked_sub(min)
+            .and_then(|n| GridUnit::try_from(n).ok())
+            .ok_or(ParseErrorKind::PatternTooLarge)
+    };
+
+    let mut cells = Vec::with_capacity(points.len());
+    let mut size = (0, 0);
+    for &(x, y) in points {
+        let point = (to_unit(x, min.0)?, to_unit(y, min.1)?);
+        size = (
+            to_unit(i64::from(point.0) + 1, 0)?.max(size.0),
+            to_unit(i64::from(point.1) + 1, 0)?.max(size.1),
+        );
+        cells.push(point);
+    }
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    cells.dedup();
+    pattern.size = size;
+    pattern.cells = cells;
+    Ok(())
+}
+
+/*Plaintext and Life 1.05 have no dedicated name or author lines, so they are kept as
+"Name: " and "Author: " comments.*/
+fn read_comment_text(pattern: &mut PatternFile, text: &str) {
+    let text = text.trim();
+    if let Some(name) = text.strip_prefix("Name:") {
+        pattern.name = Some(name.trim().to_string());
+    } else if let Some(author) = text.strip_prefix("Author:") {
+        pattern.author = Some(author.trim().to_string());
+    } else {
+        pattern.comments.push(text.to_string());
+    }
+}
+
+fn comment_texts(pattern: &PatternFile) -> Vec<String> {
+    let name = pattern.name.iter().map(|name| format!("Name: {}", name));
+    let author = pattern
+        .author
+        .iter()
+        .map(|author| format!("Author: {}", author));
+    name.chain(author)
+        .chain(pattern.comments.iter().cloned())
+        .collect()
+}
+
+/*Rows of the pattern, each as a list of the columns of its live cells.*/
+fn rows(pattern: &PatternFile) -> Vec<Vec<GridUnit>> {
+    let mut rows = vec![Vec::new(); pattern.size.1 as usize];
+    for &(x, y) in &pattern.cells {
+        rows[y as usize].push(x);
+    }
+    rows.iter_mut().for_each(|row| {
+        row.sort_unstable();
+        row.dedup();
+    });
+    rows
+}
+
+#[derive(Debug)]
+pub enum ParseErrorKind {
+    UnknownFormat,
+    MissingHeader,
+    InvalidHeader,
+    InvalidRule(RuleParseError),
+    UnexpectedCharacter(char),
+    InvalidCoordinate,
+    InvalidRunCount,
+    PatternTooLarge,
+    CellOutsidePattern,
+    MissingTerminator,
+}
+
+/*Line and column are 1-based and point at the first character that could not be read.*/
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+impl ParseError {
+    pub(crate) fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    pub fn column(&self) -> usize {
+        self.column
+    }
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl Error for ParseError {}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Parse(ParseError),
+    OutOfBounds(OutOfBounds),
+}
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl Error for ReadError {}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<OutOfBounds> for ReadError {
+    fn from(e: OutOfBounds) -> Self {
+        Self::OutOfBounds(e)
+    }
+}
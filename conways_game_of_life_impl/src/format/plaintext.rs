@@ -0,0 +1,60 @@
+use super::{
+    comment_texts, read_comment_text, read_with, rows, ParseError, ParseErrorKind, PatternFile,
+    ReadError,
+};
+use crate::{Grid, GridPoint, GridUnit};
+use std::convert::TryFrom;
+
+pub fn read<G: Grid>(grid: &mut G, s: &str, top_left: GridPoint) -> Result<PatternFile, ReadError> {
+    read_with(grid, s, top_left, parse)
+}
+
+/*Lines starting with '!' are comments; every other line is a row of '.' for dead cells
+and 'O' (or '*') for live ones. Rows may be shorter than the widest row.*/
+pub fn parse(s: &str) -> Result<PatternFile, ParseError> {
+    let mut pattern = PatternFile::default();
+    let mut size = (0, 0);
+    for (line, line_number) in s.lines().zip(1..) {
+        if let Some(comment) = line.strip_prefix('!') {
+            read_comment_text(&mut pattern, comment);
+            continue;
+        }
+        let error = |column, kind| ParseError::new(line_number, column, kind);
+        let y = GridUnit::try_from(size.1 + 1)
+            .map(|height| height - 1)
+            .map_err(|_| error(1, ParseErrorKind::PatternTooLarge))?;
+        let line = line.trim_end();
+        for (c, column) in line.chars().zip(1..) {
+            let x = GridUnit::try_from(column)
+                .map(|width| width - 1)
+                .map_err(|_| error(column, ParseErrorKind::PatternTooLarge))?;
+            match c {
+                '.' => (),
+                'O' | '*' => pattern.cells.push((x, y)),
+                c => return Err(error(column, ParseErrorKind::UnexpectedCharacter(c))),
+            }
+            size.0 = size.0.max(x as usize + 1);
+        }
+        size.1 += 1;
+    }
+    pattern.size = (size.0 as GridUnit, size.1 as GridUnit);
+    Ok(pattern)
+}
+
+pub fn write(pattern: &PatternFile) -> String {
+    let mut ret = String::new();
+    for text in comment_texts(pattern) {
+        ret.push('!');
+        ret.push_str(&text);
+        ret.push('\n');
+    }
+    for row in rows(pattern) {
+        let mut line = vec!['.'; pattern.size.0 as usize];
+        for x in row {
+            line[x as usize] = 'O';
+        }
+        ret.extend(line);
+        ret.push('\n');
+    }
+    ret
+}
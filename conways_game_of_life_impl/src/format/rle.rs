@@ -0,0 +1,231 @@
+use super::{read_with, ParseError, ParseErrorKind, PatternFile, ReadError};
+use crate::{BResult, Grid, GridPoint, GridUnit, Rule};
+use std::{convert::TryFrom, fmt::Write};
+
+const LINE_LENGTH: usize = 70;
+
+pub fn read<G: Grid>(grid: &mut G, s: &str, top_left: GridPoint) -> Result<PatternFile, ReadError> {
+    read_with(grid, s, top_left, parse)
+}
+
+pub fn parse(s: &str) -> Result<PatternFile, ParseError> {
+    let mut pattern = PatternFile::default();
+    let mut lines = s.lines().zip(1..);
+
+    loop {
+        let (line, line_number) = lines.next().ok_or_else(|| {
+            ParseError::new(s.lines().count() + 1, 1, ParseErrorKind::MissingHeader)
+        })?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            read_comment(&mut pattern, comment, line_number)?;
+            continue;
+        }
+        read_header(&mut pattern, line, line_number)?;
+        break;
+    }
+
+    let (width, height) = (pattern.size.0 as usize, pattern.size.1 as usize);
+    let (mut x, mut y) = (0, 0);
+    let mut run_opt: Option<usize> = None;
+    let mut end = (1, 1);
+    for (line, line_number) in lines {
+        for (c, column) in line.chars().zip(1..) {
+            let error = |kind| ParseError::new(line_number, column, kind);
+            match c {
+                '0'..='9' => {
+                    let digit = c as usize - '0' as usize;
+                    run_opt = Some(
+                        run_opt
+                            .unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|run| run.checked_add(digit))
+                            .ok_or_else(|| error(ParseErrorKind::InvalidRunCount))?,
+                    );
+                }
+                'b' | 'o' => {
+                    let run = run_opt.take().unwrap_or(1);
+                    if run > width - x || y >= height {
+                        return Err(error(ParseErrorKind::CellOutsidePattern));
+                    }
+                    if c == 'o' {
+                        pattern
+                            .cells
+                            .extend((x..x + run).map(|x| (x as GridUnit, y as GridUnit)));
+                    }
+                    x += run;
+                }
+                '$' => {
+                    y = y.saturating_add(run_opt.take().unwrap_or(1));
+                    x = 0;
+                }
+                '!' => {
+                    if run_opt.is_some() {
+                        return Err(error(ParseErrorKind::UnexpectedCharacter(c)));
+                    }
+                    return Ok(pattern);
+                }
+                c if c.is_whitespace() => (),
+                c => return Err(error(ParseErrorKind::UnexpectedCharacter(c))),
+            }
+        }
+        end = (line_number, line.chars().count() + 1);
+    }
+    Err(ParseError::new(
+        end.0,
+        end.1,
+        ParseErrorKind::MissingTerminator,
+    ))
+}
+
+fn read_comment(
+    pattern: &mut PatternFile,
+    comment: &str,
+    line_number: usize,
+) -> Result<(), ParseError> {
+    let mut chars = comment.chars();
+    let tag = chars.next();
+    let text = chars.as_str().trim();
+    match tag {
+        Some('N') => pattern.name = Some(text.to_string()),
+        Some('O') => pattern.author = Some(text.to_string()),
+        Some('C') | Some('c') => pattern.comments.push(text.to_string()),
+        Some('r') => {
+            pattern.rule = Some(
+                text.parse()
+                    .map_err(|e| ParseError::new(line_number, 1, ParseErrorKind::InvalidRule(e)))?,
+            )
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/*Reads "x = m, y = n, rule = abc", where the rule is optional.*/
+fn read_header(
+    pattern: &mut PatternFile,
+    line: &str,
+    line_number: usize,
+) -> Result<(), ParseError> {
+    let mut size = (None, None);
+    let mut column = 1;
+    for item in line.split(',') {
+        let indent = item.chars().count() - item.trim_start().chars().count();
+        let error = |kind| ParseError::new(line_number, column + indent, kind);
+        let (key, value) = item
+            .split_once('=')
+            .ok_or_else(|| error(ParseErrorKind::InvalidHeader))?;
+        let value = value.trim();
+        match key.trim() {
+            "x" | "y" => {
+                let n = value
+                    .parse::<u64>()
+                    .map_err(|_| error(ParseErrorKind::InvalidHeader))?;
+                let n =
+                    GridUnit::try_from(n).map_err(|_| error(ParseErrorKind::PatternTooLarge))?;
+                if key.trim() == "x" {
+                    size.0 = Some(n);
+                } else {
+                    size.1 = Some(n);
+                }
+            }
+            "rule" => {
+                pattern.rule = Some(
+                    value
+                        .parse::<Rule>()
+                        .map_err(|e| error(ParseErrorKind::InvalidRule(e)))?,
+                )
+            }
+            _ => return Err(error(ParseErrorKind::InvalidHeader)),
+        }
+        column += item.chars().count() + 1;
+    }
+    match size {
+        (Some(width), Some(height)) => {
+            pattern.size = (width, height);
+            Ok(())
+        }
+        _ => Err(ParseError::new(
+            line_number,
+            1,
+            ParseErrorKind::InvalidHeader,
+        )),
+    }
+}
+
+pub fn write_grid<G: Grid>(grid: &G) -> String {
+    write(&PatternFile::from_grid(grid))
+}
+
+pub fn write_grid_region<G: Grid>(
+    grid: &G,
+    top_left: GridPoint,
+    size: GridPoint,
+) -> BResult<String> {
+    Ok(write(&PatternFile::from_grid_region(grid, top_left, size)?))
+}
+
+/*Runs are wrapped so that no line exceeds 70 characters, without splitting a run count
+from its tag. Trailing dead cells of a row and trailing empty rows are left out.*/
+pub fn write(pattern: &PatternFile) -> String {
+    let mut ret = String::new();
+    if let Some(name) = &pattern.name {
+        writeln!(ret, "#N {}", name).unwrap();
+    }
+    if let Some(author) = &pattern.author {
+        writeln!(ret, "#O {}", author).unwrap();
+    }
+    for comment in &pattern.comments {
+        writeln!(ret, "#C {}", comment).unwrap();
+    }
+    write!(ret, "x = {}, y = {}", pattern.size.0, pattern.size.1).unwrap();
+    if let Some(rule) = pattern.rule {
+        write!(ret, ", rule = {}", rule).unwrap();
+    }
+    ret.push('\n');
+
+    let mut line = String::new();
+    let mut push_run = |run: usize, tag: char| {
+        let token = if run > 1 {
+            format!("{}{}", run, tag)
+        } else {
+            tag.to_string()
+        };
+        if line.len() + token.len() > LINE_LENGTH {
+            ret.push_str(&line);
+            ret.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    };
+
+    let mut cells = pattern.cells.clone();
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+    cells.dedup();
+    let mut position = (0, 0);
+    let mut cells = cells.into_iter().peekable();
+    while let Some((x, y)) = cells.next() {
+        if y > position.1 {
+            push_run((y - position.1) as usize, '$');
+            position = (0, y);
+        }
+        if x > position.0 {
+            push_run((x - position.0) as usize, 'b');
+        }
+        let mut run = 1;
+        while cells.peek() == Some(&(x + run, y)) {
+            cells.next();
+            run += 1;
+        }
+        push_run(run as usize, 'o');
+        position = (x + run, y);
+    }
+    push_run(1, '!');
+
+    ret.push_str(&line);
+    ret.push('\n');
+    ret
+}
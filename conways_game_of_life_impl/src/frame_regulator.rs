@@ -28,11 +28,8 @@ impl FrameRegulator {
     }
 
     pub fn fps(fps: u64) -> Result<Self, ZeroFps> {
-        if fps == 0 {
-            Err(ZeroFps)
-        } else {
-            Ok(Self::new(Duration::from_nanos(1_000_000_000 / fps)))
-        }
+        let nanos = 1_000_000_000_u64.checked_div(fps).ok_or(ZeroFps)?;
+        Ok(Self::new(Duration::from_nanos(nanos)))
     }
 
     pub fn regulate(&mut self) {
@@ -0,0 +1,451 @@
+use crate::{Grid, GridUnit, Rule, UniversePoint, UniverseUnit};
+use std::{collections::HashMap, error::Error, fmt, mem};
+
+type NodeId = u32;
+type Level = u8;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+const MIN_ROOT_LEVEL: Level = 3;
+const DEFAULT_NODE_LIMIT: usize = 1 << 22;
+
+#[derive(Clone, Copy)]
+struct Node {
+    children: [NodeId; 4],
+    level: Level,
+    population: u64,
+}
+
+#[derive(Debug)]
+pub struct UnsupportedRule(Rule);
+impl UnsupportedRule {
+    pub(crate) fn new(rule: Rule) -> Self {
+        Self(rule)
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.0
+    }
+}
+impl fmt::Display for UnsupportedRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl Error for UnsupportedRule {}
+
+/*Quadtree universe centred on (0, 0). Children are ordered nw, ne, sw, se and every node
+is canonicalised, so identical regions share one id and the result cache applies to all
+of them. Rules with B0 are rejected, as empty space would not stay empty.*/
+#[derive(Clone)]
+pub struct HashLife {
+    nodes: Vec<Node>,
+    canonical: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, Level), NodeId>,
+    empty: Vec<NodeId>,
+    root: NodeId,
+    rule: Rule,
+    generation: u64,
+    node_limit: usize,
+}
+
+impl HashLife {
+    pub fn empty() -> Self {
+        let leaf = |population| Node {
+            children: [DEAD; 4],
+            level: 0,
+            population,
+        };
+        let mut ret = Self {
+            nodes: vec![leaf(0), leaf(1)],
+            canonical: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            root: DEAD,
+            rule: Rule::default(),
+            generation: 0,
+            node_limit: DEFAULT_NODE_LIMIT,
+        };
+        ret.root = ret.empty_node(MIN_ROOT_LEVEL);
+        ret
+    }
+
+    pub fn from_grid<G: Grid>(grid: &G) -> Result<Self, UnsupportedRule> {
+        let mut ret = Self::empty();
+        ret.set_rule(grid.rule())?;
+        grid.inspect(|(x, y), grid| {
+            if grid.get_cell_unchecked((x, y)) {
+                ret.set_cell((x as UniverseUnit, y as UniverseUnit), true);
+            }
+        });
+        Ok(ret)
+    }
+
+    /*Overwrites every cell of the grid with the universe window whose top left corner is
+    at top_left.*/
+    pub fn write_to_grid<G: Grid>(&self, grid: &mut G, top_left: UniversePoint) {
+        let size = grid.size();
+        grid.inspect_mut(|point, grid| grid.set_cell_unchecked(point, false));
+        let bottom_right = (
+            top_left.0 + size.0 as UniverseUnit - 1,
+            top_left.1 + size.1 as UniverseUnit - 1,
+        );
+        self.for_each_live_cell_in((top_left, bottom_right), |(x, y)| {
+            grid.set_cell_unchecked(
+                ((x - top_left.0) as GridUnit, (y - top_left.1) as GridUnit),
+                true,
+            );
+        });
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) -> Result<(), UnsupportedRule> {
+        if rule.birth(0) {
+            return Err(UnsupportedRule::new(rule));
+        }
+        if rule != self.rule {
+            self.rule = rule;
+            self.results.clear();
+        }
+        Ok(())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    pub fn set_node_limit(&mut self, node_limit: usize) {
+        self.node_limit = node_limit;
+    }
+
+    pub fn get_cell(&self, (x, y): UniversePoint) -> bool {
+        let mut node = self.nodes[self.root as usize];
+        let half = 1i128 << (node.level - 1);
+        let (mut x, mut y) = (x as i128 + half, y as i128 + half);
+        if x < 0 || y < 0 || x >= 2 * half || y >= 2 * half {
+            return false;
+        }
+        while node.level > 0 {
+            let half = 1i128 << (node.level - 1);
+            let (east, south) = (x >= half, y >= half);
+            node = self.nodes[node.children[east as usize + 2 * south as usize] as usize];
+            x -= half * east as i128;
+            y -= half * south as i128;
+        }
+        node.population == 1
+    }
+
+    pub fn set_cell(&mut self, (x, y): UniversePoint, b: bool) {
+        loop {
+            let half = 1i128 << (self.level(self.root) - 1);
+            let (x, y) = (x as i128, y as i128);
+            if x >= -half && y >= -half && x < half && y < half {
+                self.root = self.set_node_cell(self.root, (x + half, y + half), b);
+                return;
+            }
+            self.root = self.expand(self.root);
+        }
+    }
+
+    pub fn advance(&mut self, generations: u64) {
+        for k in 0..u64::BITS as Level {
+            if generations >> k & 1 == 1 {
+                self.advance_power_of_two(k);
+            }
+        }
+        self.generation = self.generation.wrapping_add(generations);
+    }
+
+    pub fn bounding_box(&self) -> Option<(UniversePoint, UniversePoint)> {
+        let mut bounding_box: Option<(UniversePoint, UniversePoint)> = None;
+        self.for_each_live_cell(|(x, y)| {
+            bounding_box = Some(match bounding_box {
+                Some(((x_min, y_min), (x_max, y_max))) => {
+                    ((x_min.min(x), y_min.min(y)), (x_max.max(x), y_max.max(y)))
+                }
+                None => ((x, y), (x, y)),
+            });
+        });
+        bounding_box
+    }
+
+    pub fn for_each_live_cell<F: FnMut(UniversePoint)>(&self, f: F) {
+        self.for_each_live_cell_in(
+            (
+                (UniverseUnit::MIN, UniverseUnit::MIN),
+                (UniverseUnit::MAX, UniverseUnit::MAX),
+            ),
+            f,
+        );
+    }
+
+    pub fn for_each_live_cell_in<F: FnMut(UniversePoint)>(
+        &self,
+        (top_left, bottom_right): (UniversePoint, UniversePoint),
+        mut f: F,
+    ) {
+        let half = 1i128 << (self.level(self.root) - 1);
+        let rect = (
+            (top_left.0 as i128, top_left.1 as i128),
+            (bottom_right.0 as i128, bottom_right.1 as i128),
+        );
+        self.visit_live_cells(self.root, (-half, -half), rect, &mut f);
+    }
+
+    fn visit_live_cells<F: FnMut(UniversePoint)>(
+        &self,
+        node: NodeId,
+        (x, y): (i128, i128),
+        rect @ ((x_min, y_min), (x_max, y_max)): ((i128, i128), (i128, i128)),
+        f: &mut F,
+    ) {
+        let Node {
+            children,
+            level,
+            population,
+        } = self.nodes[node as usize];
+        let size = 1i128 << level;
+        if population == 0 || x > x_max || y > y_max || x + size <= x_min || y + size <= y_min {
+            return;
+        }
+        if level == 0 {
+            f((x as UniverseUnit, y as UniverseUnit));
+            return;
+        }
+        let half = size / 2;
+        for (i, &child) in children.iter().enumerate() {
+            let offset = (half * (i % 2) as i128, half * (i / 2) as i128);
+            self.visit_live_cells(child, (x + offset.0, y + offset.1), rect, f);
+        }
+    }
+
+    fn level(&self, node: NodeId) -> Level {
+        self.nodes[node as usize].level
+    }
+
+    fn children(&self, node: NodeId) -> [NodeId; 4] {
+        self.nodes[node as usize].children
+    }
+
+    fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(&node) = self.canonical.get(&children) {
+            return node;
+        }
+        let node = self.nodes.len() as NodeId;
+        self.nodes.push(Node {
+            children,
+            level: self.level(children[0]) + 1,
+            population: children.iter().fold(0u64, |population, &child| {
+                population.saturating_add(self.nodes[child as usize].population)
+            }),
+        });
+        self.canonical.insert(children, node);
+        node
+    }
+
+    fn empty_node(&mut self, level: Level) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let child = self.empty[self.empty.len() - 1];
+            let node = self.join([child; 4]);
+            self.empty.push(node);
+        }
+        self.empty[level as usize]
+    }
+
+    fn centre(&mut self, node: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.children(node);
+        self.join([
+            self.children(nw)[3],
+            self.children(ne)[2],
+            self.children(sw)[1],
+            self.children(se)[0],
+        ])
+    }
+
+    fn expand(&mut self, node: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.children(node);
+        let empty = self.empty_node(self.level(node) - 1);
+        let nw = self.join([empty, empty, empty, nw]);
+        let ne = self.join([empty, empty, ne, empty]);
+        let sw = self.join([empty, sw, empty, empty]);
+        let se = self.join([se, empty, empty, empty]);
+        self.join([nw, ne, sw, se])
+    }
+
+    fn set_node_cell(&mut self, node: NodeId, (x, y): (i128, i128), b: bool) -> NodeId {
+        let level = self.level(node);
+        if level == 0 {
+            return if b { ALIVE } else { DEAD };
+        }
+        let half = 1i128 << (level - 1);
+        let (east, south) = (x >= half, y >= half);
+        let index = east as usize + 2 * south as usize;
+        let mut children = self.children(node);
+        children[index] = self.set_node_cell(
+            children[index],
+            (x - half * east as i128, y - half * south as i128),
+            b,
+        );
+        self.join(children)
+    }
+
+    fn advance_power_of_two(&mut self, k: Level) {
+        if self.nodes.len() > self.node_limit {
+            self.collect_garbage();
+        }
+        while self.level(self.root) < k + 2 || !self.root_is_padded() {
+            self.root = self.expand(self.root);
+        }
+        self.root = self.expand(self.root);
+        self.root = self.step(self.root, k);
+    }
+
+    /*True when every live cell lies in the central quarter of the root, so the pattern
+    cannot grow past the result of stepping the root once more expanded.*/
+    fn root_is_padded(&mut self) -> bool {
+        let centre = self.centre(self.root);
+        self.nodes[centre as usize].population == self.population()
+    }
+
+    /*Returns the centre of a level n node advanced 2^k generations, where k <= n - 2.*/
+    fn step(&mut self, node: NodeId, k: Level) -> NodeId {
+        let Node {
+            children: [nw, ne, sw, se],
+            level,
+            population,
+        } = self.nodes[node as usize];
+        if population == 0 {
+            return self.empty_node(level - 1);
+        }
+        if let Some(&result) = self.results.get(&(node, k)) {
+            return result;
+        }
+
+        let result = if level == 2 {
+            self.step_base_case(node)
+        } else {
+            let [_, nw_ne, nw_sw, nw_se] = self.children(nw);
+            let [ne_nw, _, ne_sw, ne_se] = self.children(ne);
+            let [sw_nw, sw_ne, _, sw_se] = self.children(sw);
+            let [se_nw, se_ne, se_sw, _] = self.children(se);
+            let overlapping = [
+                nw,
+                self.join([nw_ne, ne_nw, nw_se, ne_sw]),
+                ne,
+                self.join([nw_sw, nw_se, sw_nw, sw_ne]),
+                self.join([nw_se, ne_sw, sw_ne, se_nw]),
+                self.join([ne_sw, ne_se, se_nw, se_ne]),
+                sw,
+                self.join([sw_ne, se_nw, sw_se, se_sw]),
+                se,
+            ];
+
+            let full_speed = k == level - 2;
+            let inner_k = if full_speed { k - 1 } else { k };
+            let mut r = [DEAD; 9];
+            for (r, &node) in r.iter_mut().zip(&overlapping) {
+                *r = if full_speed {
+                    self.step(node, inner_k)
+                } else {
+                    self.centre(node)
+                };
+            }
+
+            let quadrants = [
+                self.join([r[0], r[1], r[3], r[4]]),
+                self.join([r[1], r[2], r[4], r[5]]),
+                self.join([r[3], r[4], r[6], r[7]]),
+                self.join([r[4], r[5], r[7], r[8]]),
+            ];
+            let mut stepped = [DEAD; 4];
+            for (stepped, &quadrant) in stepped.iter_mut().zip(&quadrants) {
+                *stepped = self.step(quadrant, inner_k);
+            }
+            self.join(stepped)
+        };
+
+        self.results.insert((node, k), result);
+        result
+    }
+
+    fn step_base_case(&mut self, node: NodeId) -> NodeId {
+        let mut cells = [[false; 4]; 4];
+        for (i, &child) in self.children(node).iter().enumerate() {
+            for (j, &leaf) in self.children(child).iter().enumerate() {
+                cells[i / 2 * 2 + j / 2][i % 2 * 2 + j % 2] = leaf == ALIVE;
+            }
+        }
+
+        let mut leaves = [DEAD; 4];
+        for (i, leaf) in leaves.iter_mut().enumerate() {
+            let (x, y) = (1 + i % 2, 1 + i / 2);
+            let counter = (y - 1..=y + 1)
+                .flat_map(|scan_y| (x - 1..=x + 1).map(move |scan_x| (scan_x, scan_y)))
+                .filter(|&(scan_x, scan_y)| (scan_x != x || scan_y != y) && cells[scan_y][scan_x])
+                .count() as u8;
+            if self.rule.next_state(cells[y][x], counter) {
+                *leaf = ALIVE;
+            }
+        }
+        self.join(leaves)
+    }
+
+    /*Drops every node that is not reachable from the root, along with the result cache.*/
+    fn collect_garbage(&mut self) {
+        fn copy(
+            old_nodes: &[Node],
+            new: &mut HashLife,
+            remapped: &mut HashMap<NodeId, NodeId>,
+            node: NodeId,
+        ) -> NodeId {
+            if old_nodes[node as usize].level == 0 {
+                return node;
+            }
+            if let Some(&copied) = remapped.get(&node) {
+                return copied;
+            }
+            let mut children = old_nodes[node as usize].children;
+            for child in &mut children {
+                *child = copy(old_nodes, new, remapped, *child);
+            }
+            let copied = new.join(children);
+            remapped.insert(node, copied);
+            copied
+        }
+
+        let old_nodes = mem::take(&mut self.nodes);
+        let mut new = Self::empty();
+        let root = copy(&old_nodes, &mut new, &mut HashMap::new(), self.root);
+        self.nodes = new.nodes;
+        self.canonical = new.canonical;
+        self.empty = new.empty;
+        self.results.clear();
+        self.root = root;
+    }
+}
+
+impl Default for HashLife {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Debug for HashLife {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "HashLife {{ rule: {}, generation: {}, population: {}, nodes: {} }}",
+            self.rule,
+            self.generation,
+            self.population(),
+            self.nodes.len()
+        )
+    }
+}
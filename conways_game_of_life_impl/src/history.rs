@@ -0,0 +1,211 @@
+use crate::{Grid, GridPoint, Topology};
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+};
+
+const DEFAULT_CAPACITY: usize = 1024;
+
+/*The state recorded at generation start + period equals the one at generation start,
+moved by offset (wrapping around the torus).*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Repeat {
+    start: u64,
+    period: u64,
+    offset: (i32, i32),
+}
+impl Repeat {
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+    pub fn offset(&self) -> (i32, i32) {
+        self.offset
+    }
+    pub fn generation(&self) -> u64 {
+        self.start + self.period
+    }
+    pub fn is_translated(&self) -> bool {
+        self.offset != (0, 0)
+    }
+}
+
+struct State {
+    generation: u64,
+    size: GridPoint,
+    words: Vec<u64>,
+    hash: u64,
+    fingerprint: u64,
+    row_counts: Vec<u32>,
+    column_counts: Vec<u32>,
+}
+
+impl State {
+    fn new<G: Grid>(grid: &G, generation: u64) -> Self {
+        let size = grid.size();
+        let mut words = vec![0; (size.0 as usize * size.1 as usize).div_ceil(64)];
+        let mut row_counts = vec![0; size.1 as usize];
+        let mut column_counts = vec![0; size.0 as usize];
+        grid.inspect(|(x, y), grid| {
+            if grid.get_cell_unchecked((x, y)) {
+                let index = y as usize * size.0 as usize + x as usize;
+                words[index / 64] |= 1 << (index % 64);
+                row_counts[y as usize] += 1;
+                column_counts[x as usize] += 1;
+            }
+        });
+
+        let mut hasher = DefaultHasher::new();
+        words.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        /*Sorting the row and column populations makes the fingerprint unchanged by any
+        cyclic shift, so translated copies of a state share it.*/
+        let mut hasher = DefaultHasher::new();
+        size.hash(&mut hasher);
+        for counts in [&row_counts, &column_counts] {
+            let mut sorted = counts.clone();
+            sorted.sort_unstable();
+            sorted.hash(&mut hasher);
+        }
+        let fingerprint = hasher.finish();
+
+        Self {
+            generation,
+            size,
+            words,
+            hash,
+            fingerprint,
+            row_counts,
+            column_counts,
+        }
+    }
+
+    fn get_cell(&self, (x, y): (usize, usize)) -> bool {
+        let index = y * self.size.0 as usize + x;
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    /*Offset that moves old onto self, trying only the shifts under which the row and
+    column populations line up.*/
+    fn offset_from(&self, old: &Self, translations: bool) -> Option<(i32, i32)> {
+        if self.fingerprint != old.fingerprint {
+            return None;
+        }
+        if self.hash == old.hash && self.words == old.words {
+            return Some((0, 0));
+        }
+        if !translations {
+            return None;
+        }
+
+        let (width, height) = (self.size.0 as usize, self.size.1 as usize);
+        let x_shifts = rotations(&old.column_counts, &self.column_counts);
+        for y_shift in rotations(&old.row_counts, &self.row_counts) {
+            for &x_shift in &x_shifts {
+                let matches = (0..height).all(|y| {
+                    (0..width).all(|x| {
+                        old.get_cell((x, y))
+                            == self.get_cell(((x + x_shift) % width, (y + y_shift) % height))
+                    })
+                });
+                if matches {
+                    return Some((signed(x_shift, width), signed(y_shift, height)));
+                }
+            }
+        }
+        None
+    }
+}
+
+fn rotations(old: &[u32], new: &[u32]) -> Vec<usize> {
+    (0..old.len())
+        .filter(|&shift| {
+            old.iter()
+                .enumerate()
+                .all(|(i, &count)| new[(i + shift) % new.len()] == count)
+        })
+        .collect()
+}
+
+fn signed(shift: usize, length: usize) -> i32 {
+    if shift > length / 2 {
+        shift as i32 - length as i32
+    } else {
+        shift as i32
+    }
+}
+
+/*Remembers the most recent states of a grid to detect when it repeats. Record the grid
+once before the first update and again after every update; the history then counts
+generations from that first record. Translated repeats are only detected on the torus,
+the one topology on which a shifted copy keeps evolving as a shifted copy.*/
+pub struct History {
+    states: VecDeque<State>,
+    capacity: usize,
+    generation: u64,
+    first_repeat: Option<Repeat>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /*Only the last capacity states are kept, which bounds the longest period found.*/
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            states: VecDeque::new(),
+            capacity: capacity.max(1),
+            generation: 0,
+            first_repeat: None,
+        }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn first_repeat(&self) -> Option<Repeat> {
+        self.first_repeat
+    }
+
+    /*Call after editing the grid or changing its rule or topology, as earlier states no
+    longer lead to later ones.*/
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.generation = 0;
+        self.first_repeat = None;
+    }
+
+    /*Returns the shortest repeat that ends with this state, if any.*/
+    pub fn record<G: Grid>(&mut self, grid: &G) -> Option<Repeat> {
+        let state = State::new(grid, self.generation);
+        let translations = grid.topology() == Topology::Torus;
+        let repeat = self.states.iter().rev().find_map(|old| {
+            state.offset_from(old, translations).map(|offset| Repeat {
+                start: old.generation,
+                period: state.generation - old.generation,
+                offset,
+            })
+        });
+
+        if self.states.len() == self.capacity {
+            self.states.pop_front();
+        }
+        self.states.push_back(state);
+        self.generation += 1;
+        if self.first_repeat.is_none() {
+            self.first_repeat = repeat;
+        }
+        repeat
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
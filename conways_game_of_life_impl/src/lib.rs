@@ -1,17 +1,53 @@
+mod backend;
+mod bit_packed;
+pub mod catalog;
+mod chunked;
+pub mod color;
 pub mod config;
+mod export;
+pub mod format;
 mod frame_regulator;
-
-use frame_regulator::FrameRegulator;
-pub use frame_regulator::ZeroFps;
+mod hashlife;
+mod history;
+mod pattern;
+mod rule;
+mod simulation;
+mod soup;
+mod sparse;
+mod stats;
+mod step;
+mod topology;
+mod undo;
+
+pub use backend::{Backend, UnknownBackend};
+pub use bit_packed::GridBitPacked;
+pub use chunked::GridChunked;
+pub use export::{write_ppm, ExportError, Exporter};
+pub use frame_regulator::{FrameRegulator, ZeroFps};
+pub use hashlife::{HashLife, UnsupportedRule};
+pub use history::{History, Repeat};
+pub use pattern::{Pattern, StampMode};
+pub use rule::{Rule, RuleParseError};
+pub use simulation::Simulation;
+pub use soup::{Density, Soup, SoupError, Symmetry};
+pub use sparse::SparseUniverse;
+use stats::StatsCounter;
+pub use stats::{GenerationStats, StatsSeries};
 use std::{
     alloc::{self, Layout},
     error::Error,
-    fmt, mem,
+    fmt, mem, thread,
 };
+pub use step::{Stop, StopCondition, StopReason};
+pub use topology::Topology;
+pub use undo::UndoHistory;
 
 pub type GridUnit = u16;
 pub type GridPoint = (GridUnit, GridUnit);
 
+pub type UniverseUnit = i64;
+pub type UniversePoint = (UniverseUnit, UniverseUnit);
+
 fn grid_point_contained(point: GridPoint, size: GridPoint) -> bool {
     point.0 < size.0 && point.1 < size.1
 }
@@ -38,72 +74,185 @@ impl Error for OutOfBounds {}
 
 pub type BResult<T> = Result<T, OutOfBounds>;
 
+/*The far corner is saturated to GridUnit::MAX when reporting a region that overflows it.*/
+fn check_region<G: Grid + ?Sized>(
+    grid: &G,
+    (x, y): GridPoint,
+    (width, height): GridPoint,
+) -> BResult<()> {
+    let size = grid.size();
+    let corner = (
+        x as usize + (width as usize).max(1) - 1,
+        y as usize + (height as usize).max(1) - 1,
+    );
+    if corner.0 < size.0 as usize && corner.1 < size.1 as usize {
+        Ok(())
+    } else {
+        Err(OutOfBounds {
+            point: (
+                corner.0.min(GridUnit::MAX as usize) as GridUnit,
+                corner.1.min(GridUnit::MAX as usize) as GridUnit,
+            ),
+            size,
+        })
+    }
+}
+
 pub enum SetLineOpt {
     Horizontal,
     Vertical,
 }
 
-mod private {
-    use super::*;
-    pub trait GridPrivate {
-        fn _size(&self) -> GridPoint;
-        fn _frame_regulator_opt(&mut self) -> &mut Option<FrameRegulator>;
-        fn _get_next_cell_unchecked_mut(&mut self, point: GridPoint) -> &mut bool;
+/*The storage a grid runs on: two buffers of cells (the current generation and the next
+one) plus the rule, topology and generation count. Implement it for a type, then add an
+empty impl Grid for that type to get the full Grid API, with update running the default
+stepping loop against the storage.
+
+Grid's methods are the ones to call; these are the hooks they are built from, which is
+why they are prefixed with an underscore. Points passed to the unchecked cell methods
+always lie inside _size. Reads see the current buffer only, and writes to the next buffer
+must not show through until _swap_buffers exchanges the two, as the next generation is
+written while the current one is still being read. _box_clone backs Clone for DynGrid and
+is normally Box::new(self.clone()).
+
+Storages keeping the next buffer as one Vec<bool> of rows, top to bottom, can lend it out
+through _take_next_cells so update_parallel splits it into disjoint rows and writes each
+band in place. The grid is read but not written until _restore_next_cells hands the buffer
+back. Other storages return None and have the new generation written back cell by cell.*/
+pub trait GridStorage {
+    fn _size(&self) -> GridPoint;
+    fn _get_cell_unchecked(&self, point: GridPoint) -> bool;
+    fn _set_cell_unchecked(&mut self, point: GridPoint, b: bool);
+    fn _set_next_cell_unchecked(&mut self, point: GridPoint, b: bool);
+    fn _swap_buffers(&mut self);
+    fn _rule(&self) -> &Rule;
+    fn _rule_mut(&mut self) -> &mut Rule;
+    fn _topology(&self) -> &Topology;
+    fn _topology_mut(&mut self) -> &mut Topology;
+    fn _generation(&self) -> u64;
+    fn _generation_mut(&mut self) -> &mut u64;
+    fn _box_clone(&self) -> Box<dyn Grid>;
+
+    fn _take_next_cells(&mut self) -> Option<Vec<bool>> {
+        None
+    }
+
+    fn _restore_next_cells(&mut self, _cells: Vec<bool>) {}
+
+    fn _finish_update(&mut self) {
+        self._swap_buffers();
+        *self._generation_mut() += 1;
+    }
 
-        fn _regulate_frame(&mut self) {
-            if let Some(frame_regulator) = &mut self._frame_regulator_opt() {
-                frame_regulator.regulate();
+    fn _g_fmt(&self, tc: char, fc: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self._size();
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let b = self._get_cell_unchecked((x, y));
+                write!(f, "{}", if b { tc } else { fc })?;
             }
+            writeln!(f)?;
         }
-
-        fn _g_fmt(&self, _tc: char, _fc: char, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            Ok(())
-        }
+        Ok(())
     }
 }
-use private::GridPrivate;
 
-pub trait Grid: GridPrivate {
-    fn update(&mut self);
+pub trait Grid: GridStorage + Sync {
+    fn update(&mut self) {
+        set_next_state(self);
+        self._finish_update();
+    }
 
-    fn get_cell_unchecked(&self, point: GridPoint) -> bool;
-    fn get_cell_unchecked_mut(&mut self, point: GridPoint) -> &mut bool;
+    /*Same as update, also counting the population, births, deaths and live bounding box
+    of the new generation while it is computed.*/
+    fn update_with_stats(&mut self) -> GenerationStats {
+        let counter = set_next_state_counted::<Self, true>(self);
+        self._finish_update();
+        counter.finish(self.generation())
+    }
+
+    /*Same result as update, with the rows split into bands computed on up to threads
+    threads.*/
+    fn update_parallel(&mut self, threads: usize) {
+        set_next_state_parallel(self, threads);
+        self._finish_update();
+    }
+
+    /*Runs n generations.*/
+    fn step(&mut self, n: u64) -> Stop
+    where
+        Self: Sized,
+    {
+        step::step_until(self, n, &[])
+    }
+
+    /*Same as step, stopping early on the first generation that meets any of the
+    conditions, checked in the order given.*/
+    fn step_until(&mut self, n: u64, conditions: &[StopCondition]) -> Stop
+    where
+        Self: Sized,
+    {
+        step::step_until(self, n, conditions)
+    }
+
+    fn get_cell_unchecked(&self, point: GridPoint) -> bool {
+        self._get_cell_unchecked(point)
+    }
+
+    fn set_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        self._set_cell_unchecked(point, b)
+    }
 
     fn size(&self) -> GridPoint {
         self._size()
     }
 
-    fn set_fps(&mut self, fps: u64) {
-        *self._frame_regulator_opt() = if fps != 0 {
-            Some(FrameRegulator::fps(fps).unwrap())
-        } else {
-            None
-        };
+    fn generation(&self) -> u64 {
+        self._generation()
     }
 
-    fn set_cell(&mut self, point: GridPoint, b: bool) -> BResult<()> {
-        *self.get_cell_mut(point)? = b;
-        Ok(())
+    fn set_generation(&mut self, generation: u64) {
+        *self._generation_mut() = generation;
     }
 
-    fn get_cell(&self, point: GridPoint) -> bool {
-        if grid_point_contained(point, self.size()) {
-            self.get_cell_unchecked(point)
-        } else {
-            false
-        }
+    fn rule(&self) -> Rule {
+        *self._rule()
+    }
+
+    fn set_rule(&mut self, rule: Rule) {
+        *self._rule_mut() = rule;
     }
 
-    fn get_cell_mut(&mut self, point: GridPoint) -> BResult<&mut bool> {
+    fn topology(&self) -> Topology {
+        *self._topology()
+    }
+
+    fn set_topology(&mut self, topology: Topology) {
+        *self._topology_mut() = topology;
+    }
+
+    fn set_cell(&mut self, point: GridPoint, b: bool) -> BResult<()> {
         let size = self.size();
         if grid_point_contained(point, size) {
-            Ok(self.get_cell_unchecked_mut(point))
+            self.set_cell_unchecked(point, b);
+            Ok(())
         } else {
             Err(OutOfBounds { point, size })
         }
     }
 
-    fn inspect<F: FnMut(GridPoint, &Self)>(&self, mut f: F) {
+    fn get_cell(&self, point: GridPoint) -> bool {
+        if grid_point_contained(point, self.size()) {
+            self.get_cell_unchecked(point)
+        } else {
+            false
+        }
+    }
+
+    fn inspect<F: FnMut(GridPoint, &Self)>(&self, mut f: F)
+    where
+        Self: Sized,
+    {
         let size = self.size();
         for y in 0..size.1 {
             for x in 0..size.0 {
@@ -112,10 +261,10 @@ pub trait Grid: GridPrivate {
         }
     }
 
-    fn try_inspect<E, F: FnMut(GridPoint, &Self) -> Result<(), E>>(
-        &self,
-        mut f: F,
-    ) -> Result<(), E> {
+    fn try_inspect<E, F: FnMut(GridPoint, &Self) -> Result<(), E>>(&self, mut f: F) -> Result<(), E>
+    where
+        Self: Sized,
+    {
         let size = self.size();
         for y in 0..size.1 {
             for x in 0..size.0 {
@@ -125,7 +274,10 @@ pub trait Grid: GridPrivate {
         Ok(())
     }
 
-    fn inspect_mut<F: FnMut(GridPoint, &mut Self)>(&mut self, mut f: F) {
+    fn inspect_mut<F: FnMut(GridPoint, &mut Self)>(&mut self, mut f: F)
+    where
+        Self: Sized,
+    {
         let size = self.size();
         for y in 0..size.1 {
             for x in 0..size.0 {
@@ -137,7 +289,10 @@ pub trait Grid: GridPrivate {
     fn try_inspect_mut<E, F: FnMut(GridPoint, &mut Self) -> Result<(), E>>(
         &mut self,
         mut f: F,
-    ) -> Result<(), E> {
+    ) -> Result<(), E>
+    where
+        Self: Sized,
+    {
         let size = self.size();
         for y in 0..size.1 {
             for x in 0..size.0 {
@@ -147,6 +302,47 @@ pub trait Grid: GridPrivate {
         Ok(())
     }
 
+    /*Combines the pattern with the grid, its top left corner at (x, y), which must be in
+    the grid. Cells past the right or bottom edge are glued on across it, as the grid's
+    topology would move them there, so on a plane they are left out. On the other
+    topologies the pattern must be no bigger than the grid, so no two cells land on the
+    same one.*/
+    fn stamp(&mut self, pattern: &Pattern, (x, y): GridPoint, mode: StampMode) -> BResult<()> {
+        check_region(self, (x, y), (1, 1))?;
+        let size = self.size();
+        let topology = self.topology();
+        if topology != Topology::Plane {
+            check_region(self, (0, 0), pattern.size())?;
+        }
+        let map = |(x_offset, y_offset): GridPoint| {
+            topology.map_point(
+                (x as i32 + x_offset as i32, y as i32 + y_offset as i32),
+                size,
+            )
+        };
+
+        if mode == StampMode::Overwrite {
+            for y_offset in 0..pattern.size().1 {
+                for x_offset in 0..pattern.size().0 {
+                    if let Some(point) = map((x_offset, y_offset)) {
+                        self.set_cell_unchecked(point, false);
+                    }
+                }
+            }
+        }
+        for &offset in pattern.cells() {
+            if let Some(point) = map(offset) {
+                let b = match mode {
+                    StampMode::Overwrite | StampMode::Or => true,
+                    StampMode::Xor => !self.get_cell_unchecked(point),
+                    StampMode::Clear => false,
+                };
+                self.set_cell_unchecked(point, b);
+            }
+        }
+        Ok(())
+    }
+
     fn set_hline(
         &mut self,
         start: GridUnit,
@@ -189,66 +385,79 @@ pub trait Grid: GridPrivate {
         Ok(())
     }
 
-    fn set_line(&mut self, mut start: GridPoint, mut end: GridPoint, b: bool) -> BResult<()> {
-        let axis = if (end.1 as i32 - start.1 as i32).abs() > (end.0 as i32 - start.0 as i32).abs()
-        {
-            mem::swap(&mut start.0, &mut start.1);
-            mem::swap(&mut end.0, &mut end.1);
-            if end.0 < start.0 {
-                mem::swap(&mut start, &mut end);
-            }
-            false
-        } else {
-            if end.0 < start.0 {
-                mem::swap(&mut start, &mut end);
-            }
-            true
-        };
-
-        let mut set_cell = |v, o_v, x_axis| -> BResult<()> {
-            self.set_cell(if x_axis { (v, o_v) } else { (o_v, v) }, b)
-        };
+    fn set_line(&mut self, start: GridPoint, end: GridPoint, b: bool) -> BResult<()> {
+        let to_universe = |(x, y): GridPoint| (x as UniverseUnit, y as UniverseUnit);
+        for_each_line_point(to_universe(start), to_universe(end), |(x, y)| {
+            self.set_cell((x as GridUnit, y as GridUnit), b)
+        })
+    }
+}
 
-        if end.0 - start.0 == 0 {
-            set_cell(start.0, start.1, axis)?;
-        } else {
-            let start_x = start.0 as f64;
-            let start_y = start.1 as f64;
-            let m = (end.1 as f64 - start_y) / (end.0 as f64 - start_x);
-            let c = start_y - m * start_x;
-            for v in start.0..=end.0 {
-                set_cell(v, (m * v as f64 + c).round() as GridUnit, axis)?;
-            }
+/*Bresenham's line, with deltas widened to i128 so any two points in the universe can be
+joined. Coordinates wrap like the sparse universe's do.*/
+fn for_each_line_point<E, F: FnMut(UniversePoint) -> Result<(), E>>(
+    mut start: UniversePoint,
+    mut end: UniversePoint,
+    mut f: F,
+) -> Result<(), E> {
+    let delta = |start: UniverseUnit, end: UniverseUnit| end as i128 - start as i128;
+    let axis = if delta(start.1, end.1).abs() > delta(start.0, end.0).abs() {
+        mem::swap(&mut start.0, &mut start.1);
+        mem::swap(&mut end.0, &mut end.1);
+        if end.0 < start.0 {
+            mem::swap(&mut start, &mut end);
         }
-        Ok(())
+        false
+    } else {
+        if end.0 < start.0 {
+            mem::swap(&mut start, &mut end);
+        }
+        true
+    };
+
+    let mut set_cell = |v, o_v, x_axis| f(if x_axis { (v, o_v) } else { (o_v, v) });
+
+    let dv = delta(start.0, end.0);
+    let do_v = delta(start.1, end.1);
+    let step = do_v.signum() as UniverseUnit;
+    let (mut v, mut o_v) = start;
+    let mut error = 0;
+    for _ in 0..=dv {
+        set_cell(v, o_v, axis)?;
+        /*Moving to the next row once the line is half way there rounds as the line's
+        exact position would.*/
+        error += do_v.abs();
+        if 2 * error >= dv {
+            o_v = o_v.wrapping_add(step);
+            error -= dv;
+        }
+        v = v.wrapping_add(1);
     }
+    Ok(())
 }
 
-fn next_cell_state_scan_wrap_around<G: Grid>(grid: &G, (x, y): GridPoint) -> bool {
+fn next_cell_state_edge<G: Grid + ?Sized>(grid: &G, (x, y): GridPoint) -> bool {
     let size = grid.size();
-
-    fn get_range(v: GridUnit, size: GridUnit) -> [GridUnit; 3] {
-        let v = v as i32;
-        let range = size as i32 - 1;
-        [
-            if v == 0 { range } else { v - 1 } as GridUnit,
-            v as GridUnit,
-            if v == range { 0 } else { v + 1 } as GridUnit,
-        ]
-    }
-    let x_range = get_range(x, size.0);
-    let y_range = get_range(y, size.1);
+    let topology = grid._topology();
     let mut counter = 0;
 
-    for &y_scan in &y_range {
-        for &x_scan in &x_range {
-            if (x_scan != x || y_scan != y) && grid.get_cell_unchecked((x_scan, y_scan)) {
-                counter += 1;
+    for y_offset in -1..=1 {
+        for x_offset in -1..=1 {
+            if x_offset == 0 && y_offset == 0 {
+                continue;
+            }
+            if let Some(point) =
+                topology.map_point((x as i32 + x_offset, y as i32 + y_offset), size)
+            {
+                if grid.get_cell_unchecked(point) {
+                    counter += 1;
+                }
             }
         }
     }
 
-    counter == 3 || (counter == 2 && grid.get_cell_unchecked((x, y)))
+    grid._rule()
+        .next_state(grid.get_cell_unchecked((x, y)), counter)
 }
 
 #[allow(dead_code)]
@@ -285,58 +494,143 @@ fn next_cell_state_individual<G: Grid>(grid: &G, (x, y): GridPoint) -> bool {
         counter += 1;
     }
 
-    counter == 3 || (counter == 2 && grid.get_cell_unchecked((x, y)))
+    grid._rule()
+        .next_state(grid.get_cell_unchecked((x, y)), counter)
+}
+
+/*Always inlined into the update loops, which the compiler can then vectorise. Left to
+its own heuristics it sometimes emits a call per cell, making updates several times slower.*/
+#[inline(always)]
+fn next_cell_state_interior<G: Grid + ?Sized>(grid: &G, rule: &Rule, (x, y): GridPoint) -> bool {
+    let mut counter = 0;
+
+    if grid.get_cell_unchecked((x - 1, y - 1)) {
+        counter += 1;
+    }
+    if grid.get_cell_unchecked((x, y - 1)) {
+        counter += 1;
+    }
+    if grid.get_cell_unchecked((x + 1, y - 1)) {
+        counter += 1;
+    }
+
+    if grid.get_cell_unchecked((x - 1, y)) {
+        counter += 1;
+    }
+    if grid.get_cell_unchecked((x + 1, y)) {
+        counter += 1;
+    }
+
+    if grid.get_cell_unchecked((x - 1, y + 1)) {
+        counter += 1;
+    }
+    if grid.get_cell_unchecked((x, y + 1)) {
+        counter += 1;
+    }
+    if grid.get_cell_unchecked((x + 1, y + 1)) {
+        counter += 1;
+    }
+    rule.next_state(grid.get_cell_unchecked((x, y)), counter)
+}
+
+fn set_next_state<G: Grid + ?Sized>(grid: &mut G) {
+    set_next_state_counted::<G, false>(grid);
 }
 
-fn set_next_state<G: Grid>(grid: &mut G) {
+/*With COUNT, every cell is also counted into the returned stats just before its next
+state is written. Without it the counting compiles away, leaving the plain update loop.*/
+fn set_next_state_counted<G: Grid + ?Sized, const COUNT: bool>(grid: &mut G) -> StatsCounter {
     let size = grid.size();
+    let rule = grid.rule();
     let x_max = size.0 - 1;
     let y_max = size.1 - 1;
+    let mut counter = StatsCounter::default();
+    let mut set = |grid: &mut G, point, next| {
+        if COUNT {
+            counter.count_cell(point, grid.get_cell_unchecked(point), next);
+        }
+        grid._set_next_cell_unchecked(point, next);
+    };
 
     for y in 1..y_max {
         for x in 1..x_max {
-            let mut counter = 0;
+            let next = next_cell_state_interior(grid, &rule, (x, y));
+            set(grid, (x, y), next);
+        }
+    }
 
-            if grid.get_cell_unchecked((x - 1, y - 1)) {
-                counter += 1;
-            }
-            if grid.get_cell_unchecked((x, y - 1)) {
-                counter += 1;
-            }
-            if grid.get_cell_unchecked((x + 1, y - 1)) {
-                counter += 1;
-            }
+    for x in 0..size.0 {
+        let next = next_cell_state_edge(grid, (x, 0));
+        set(grid, (x, 0), next);
+        if y_max != 0 {
+            let next = next_cell_state_edge(grid, (x, y_max));
+            set(grid, (x, y_max), next);
+        }
+    }
+    for y in 1..y_max {
+        let next = next_cell_state_edge(grid, (0, y));
+        set(grid, (0, y), next);
+        if x_max != 0 {
+            let next = next_cell_state_edge(grid, (x_max, y));
+            set(grid, (x_max, y), next);
+        }
+    }
+    counter
+}
 
-            if grid.get_cell_unchecked((x - 1, y)) {
-                counter += 1;
-            }
-            if grid.get_cell_unchecked((x + 1, y)) {
-                counter += 1;
+/*Splits the rows into at most threads bands. Only the current buffer is read while the
+bands are computed, so they write straight into the next buffer when the storage lends it
+out. Otherwise they write into a buffer of their own, copied to the next buffer once every
+thread has joined.*/
+fn set_next_state_parallel<G: Grid + ?Sized>(grid: &mut G, threads: usize) {
+    let size = grid.size();
+    let rule = grid.rule();
+    let width = size.0 as usize;
+    let band_height = (size.1 as usize).div_ceil(threads.max(1)).max(1);
+    let lent_cells = grid._take_next_cells();
+    let lent = lent_cells.is_some();
+    let mut next_cells = lent_cells.unwrap_or_else(|| vec![false; width * size.1 as usize]);
+
+    {
+        let grid = &*grid;
+        thread::scope(|scope| {
+            for (band_index, band) in next_cells.chunks_mut(band_height * width).enumerate() {
+                scope.spawn(move || {
+                    for (row_index, row) in band.chunks_mut(width).enumerate() {
+                        let y = (band_index * band_height + row_index) as GridUnit;
+                        set_next_row(grid, &rule, y, row);
+                    }
+                });
             }
+        });
+    }
 
-            if grid.get_cell_unchecked((x - 1, y + 1)) {
-                counter += 1;
-            }
-            if grid.get_cell_unchecked((x, y + 1)) {
-                counter += 1;
-            }
-            if grid.get_cell_unchecked((x + 1, y + 1)) {
-                counter += 1;
-            }
-            *grid._get_next_cell_unchecked_mut((x, y)) =
-                counter == 3 || (counter == 2 && grid.get_cell_unchecked((x, y)));
+    if lent {
+        grid._restore_next_cells(next_cells);
+    } else {
+        for (i, next) in next_cells.into_iter().enumerate() {
+            let point = ((i % width) as GridUnit, (i / width) as GridUnit);
+            grid._set_next_cell_unchecked(point, next);
         }
     }
+}
 
-    for x in 0..size.0 {
-        *grid._get_next_cell_unchecked_mut((x, 0)) = next_cell_state_scan_wrap_around(grid, (x, 0));
-        *grid._get_next_cell_unchecked_mut((x, y_max)) =
-            next_cell_state_scan_wrap_around(grid, (x, y_max));
+/*One row of set_next_state_parallel, its interior kept apart from the edges like
+set_next_state's so it vectorises the same way.*/
+fn set_next_row<G: Grid + ?Sized>(grid: &G, rule: &Rule, y: GridUnit, row: &mut [bool]) {
+    let x_max = row.len() - 1;
+    if y == 0 || y == grid.size().1 - 1 {
+        for (x, cell) in row.iter_mut().enumerate() {
+            *cell = next_cell_state_edge(grid, (x as GridUnit, y));
+        }
+        return;
     }
-    for y in 1..y_max {
-        *grid._get_next_cell_unchecked_mut((0, y)) = next_cell_state_scan_wrap_around(grid, (0, y));
-        *grid._get_next_cell_unchecked_mut((x_max, y)) =
-            next_cell_state_scan_wrap_around(grid, (x_max, y));
+    for x in 1..x_max as GridUnit {
+        row[x as usize] = next_cell_state_interior(grid, rule, (x, y));
+    }
+    row[0] = next_cell_state_edge(grid, (0, y));
+    if x_max != 0 {
+        row[x_max] = next_cell_state_edge(grid, (x_max as GridUnit, y));
     }
 }
 
@@ -347,7 +641,9 @@ pub struct Grid1dVec {
     size: GridPoint,
     current_vec: Grid1dVecContainer,
     next_vec: Grid1dVecContainer,
-    frame_regulator_opt: Option<FrameRegulator>,
+    rule: Rule,
+    topology: Topology,
+    generation: u64,
 }
 
 impl Grid1dVec {
@@ -357,7 +653,9 @@ impl Grid1dVec {
             size,
             current_vec: next_vec.clone(),
             next_vec,
-            frame_regulator_opt: None,
+            rule: Rule::default(),
+            topology: Topology::default(),
+            generation: 0,
         }
     }
 
@@ -370,18 +668,63 @@ impl Grid1dVec {
     }
 }
 
-impl GridPrivate for Grid1dVec {
+impl GridStorage for Grid1dVec {
     fn _size(&self) -> GridPoint {
         self.size
     }
 
-    fn _frame_regulator_opt(&mut self) -> &mut Option<FrameRegulator> {
-        &mut self.frame_regulator_opt
+    fn _rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    fn _rule_mut(&mut self) -> &mut Rule {
+        &mut self.rule
+    }
+
+    fn _topology(&self) -> &Topology {
+        &self.topology
     }
 
-    fn _get_next_cell_unchecked_mut(&mut self, point: GridPoint) -> &mut bool {
+    fn _topology_mut(&mut self) -> &mut Topology {
+        &mut self.topology
+    }
+
+    fn _generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn _generation_mut(&mut self) -> &mut u64 {
+        &mut self.generation
+    }
+
+    fn _get_cell_unchecked(&self, point: GridPoint) -> bool {
+        self.current_vec[self.get_index(point)]
+    }
+
+    fn _set_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        let index = self.get_index(point);
+        self.current_vec[index] = b;
+    }
+
+    fn _set_next_cell_unchecked(&mut self, point: GridPoint, b: bool) {
         let index = self.get_index(point);
-        &mut self.next_vec[index]
+        self.next_vec[index] = b;
+    }
+
+    fn _swap_buffers(&mut self) {
+        mem::swap(&mut self.current_vec, &mut self.next_vec);
+    }
+
+    fn _take_next_cells(&mut self) -> Option<Vec<bool>> {
+        Some(mem::take(&mut self.next_vec))
+    }
+
+    fn _restore_next_cells(&mut self, cells: Vec<bool>) {
+        self.next_vec = cells;
+    }
+
+    fn _box_clone(&self) -> Box<dyn Grid> {
+        Box::new(self.clone())
     }
 
     fn _g_fmt(&self, tc: char, fc: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -398,17 +741,7 @@ impl GridPrivate for Grid1dVec {
 impl Grid for Grid1dVec {
     fn update(&mut self) {
         set_next_state(self);
-        mem::swap(&mut self.current_vec, &mut self.next_vec);
-        self._regulate_frame();
-    }
-
-    fn get_cell_unchecked(&self, point: GridPoint) -> bool {
-        self.current_vec[self.get_index(point)]
-    }
-
-    fn get_cell_unchecked_mut(&mut self, point: GridPoint) -> &mut bool {
-        let index = self.get_index(point);
-        &mut self.current_vec[index]
+        self._finish_update();
     }
 }
 
@@ -431,7 +764,9 @@ pub struct Grid2dVec {
     size: GridPoint,
     current_vec: Grid2dVecContainer,
     next_vec: Grid2dVecContainer,
-    frame_regulator_opt: Option<FrameRegulator>,
+    rule: Rule,
+    topology: Topology,
+    generation: u64,
 }
 
 impl Grid2dVec {
@@ -441,22 +776,60 @@ impl Grid2dVec {
             size,
             current_vec: next_vec.clone(),
             next_vec,
-            frame_regulator_opt: None,
+            rule: Rule::default(),
+            topology: Topology::default(),
+            generation: 0,
         }
     }
 }
 
-impl GridPrivate for Grid2dVec {
+impl GridStorage for Grid2dVec {
     fn _size(&self) -> GridPoint {
         self.size
     }
 
-    fn _frame_regulator_opt(&mut self) -> &mut Option<FrameRegulator> {
-        &mut self.frame_regulator_opt
+    fn _rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    fn _rule_mut(&mut self) -> &mut Rule {
+        &mut self.rule
+    }
+
+    fn _topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    fn _topology_mut(&mut self) -> &mut Topology {
+        &mut self.topology
+    }
+
+    fn _generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn _generation_mut(&mut self) -> &mut u64 {
+        &mut self.generation
+    }
+
+    fn _get_cell_unchecked(&self, (x, y): GridPoint) -> bool {
+        self.current_vec[y as usize][x as usize]
+    }
+
+    fn _set_cell_unchecked(&mut self, (x, y): GridPoint, b: bool) {
+        self.current_vec[y as usize][x as usize] = b;
+    }
+
+    fn _set_next_cell_unchecked(&mut self, (x, y): GridPoint, b: bool) {
+        self.next_vec[y as usize][x as usize] = b;
     }
 
-    fn _get_next_cell_unchecked_mut(&mut self, (x, y): GridPoint) -> &mut bool {
-        &mut self.next_vec[y as usize][x as usize]
+    fn _swap_buffers(&mut self) {
+        mem::swap(&mut self.current_vec, &mut self.next_vec);
+    }
+
+    fn _box_clone(&self) -> Box<dyn Grid> {
+        Box::new(self.clone())
     }
 
     fn _g_fmt(&self, tc: char, fc: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -473,16 +846,7 @@ impl GridPrivate for Grid2dVec {
 impl Grid for Grid2dVec {
     fn update(&mut self) {
         set_next_state(self);
-        mem::swap(&mut self.current_vec, &mut self.next_vec);
-        self._regulate_frame();
-    }
-
-    fn get_cell_unchecked(&self, (x, y): GridPoint) -> bool {
-        self.current_vec[y as usize][x as usize]
-    }
-
-    fn get_cell_unchecked_mut(&mut self, (x, y): GridPoint) -> &mut bool {
-        &mut self.current_vec[y as usize][x as usize]
+        self._finish_update();
     }
 }
 
@@ -503,7 +867,9 @@ type Grid2dArrContainer<const WIDTH: usize, const HEIGHT: usize> = [[bool; WIDTH
 pub struct Grid2dArr<const WIDTH: usize, const HEIGHT: usize> {
     current_arr: Grid2dArrContainer<WIDTH, HEIGHT>,
     next_arr: Grid2dArrContainer<WIDTH, HEIGHT>,
-    frame_regulator_opt: Option<FrameRegulator>,
+    rule: Rule,
+    topology: Topology,
+    generation: u64,
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Grid2dArr<WIDTH, HEIGHT> {
@@ -517,22 +883,74 @@ impl<const WIDTH: usize, const HEIGHT: usize> Grid2dArr<WIDTH, HEIGHT> {
                 ret.next_arr[y][x] = false;
             }
         }
-        ret.frame_regulator_opt = None;
+        ret.rule = Rule::default();
+        ret.topology = Topology::default();
+        ret.generation = 0;
+        ret
+    }
+
+    fn boxed_clone(&self) -> Box<Self> {
+        let mut ret = unsafe { Box::from_raw(alloc::alloc(Layout::new::<Self>()) as *mut Self) };
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                ret.current_arr[y][x] = self.current_arr[y][x];
+                ret.next_arr[y][x] = self.next_arr[y][x];
+            }
+        }
+        ret.rule = self.rule;
+        ret.topology = self.topology;
+        ret.generation = self.generation;
         ret
     }
 }
 
-impl<const WIDTH: usize, const HEIGHT: usize> private::GridPrivate for Grid2dArr<WIDTH, HEIGHT> {
+impl<const WIDTH: usize, const HEIGHT: usize> GridStorage for Grid2dArr<WIDTH, HEIGHT> {
     fn _size(&self) -> GridPoint {
         (WIDTH as GridUnit, HEIGHT as GridUnit)
     }
 
-    fn _frame_regulator_opt(&mut self) -> &mut Option<FrameRegulator> {
-        &mut self.frame_regulator_opt
+    fn _rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    fn _rule_mut(&mut self) -> &mut Rule {
+        &mut self.rule
+    }
+
+    fn _topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    fn _topology_mut(&mut self) -> &mut Topology {
+        &mut self.topology
     }
 
-    fn _get_next_cell_unchecked_mut(&mut self, (x, y): GridPoint) -> &mut bool {
-        &mut self.next_arr[y as usize][x as usize]
+    fn _generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn _generation_mut(&mut self) -> &mut u64 {
+        &mut self.generation
+    }
+
+    fn _get_cell_unchecked(&self, (x, y): GridPoint) -> bool {
+        self.current_arr[y as usize][x as usize]
+    }
+
+    fn _set_cell_unchecked(&mut self, (x, y): GridPoint, b: bool) {
+        self.current_arr[y as usize][x as usize] = b;
+    }
+
+    fn _set_next_cell_unchecked(&mut self, (x, y): GridPoint, b: bool) {
+        self.next_arr[y as usize][x as usize] = b;
+    }
+
+    fn _swap_buffers(&mut self) {
+        self.current_arr = self.next_arr;
+    }
+
+    fn _box_clone(&self) -> Box<dyn Grid> {
+        Self::boxed_clone(self)
     }
 
     fn _g_fmt(&self, tc: char, fc: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -549,16 +967,7 @@ impl<const WIDTH: usize, const HEIGHT: usize> private::GridPrivate for Grid2dArr
 impl<const WIDTH: usize, const HEIGHT: usize> Grid for Grid2dArr<WIDTH, HEIGHT> {
     fn update(&mut self) {
         set_next_state(self);
-        self.current_arr = self.next_arr;
-        self._regulate_frame();
-    }
-
-    fn get_cell_unchecked(&self, (x, y): GridPoint) -> bool {
-        self.current_arr[y as usize][x as usize]
-    }
-
-    fn get_cell_unchecked_mut(&mut self, (x, y): GridPoint) -> &mut bool {
-        &mut self.current_arr[y as usize][x as usize]
+        self._finish_update();
     }
 }
 
@@ -574,21 +983,65 @@ impl<const WIDTH: usize, const HEIGHT: usize> fmt::Debug for Grid2dArr<WIDTH, HE
     }
 }
 
-impl<G: GridPrivate> GridPrivate for Box<G> {
+impl<G: GridStorage + ?Sized> GridStorage for Box<G> {
     fn _size(&self) -> GridPoint {
         G::_size(self)
     }
 
-    fn _frame_regulator_opt(&mut self) -> &mut Option<FrameRegulator> {
-        G::_frame_regulator_opt(self)
+    fn _get_cell_unchecked(&self, point: GridPoint) -> bool {
+        G::_get_cell_unchecked(self, point)
     }
 
-    fn _get_next_cell_unchecked_mut(&mut self, point: GridPoint) -> &mut bool {
-        G::_get_next_cell_unchecked_mut(self, point)
+    fn _set_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        G::_set_cell_unchecked(self, point, b)
     }
 
-    fn _regulate_frame(&mut self) {
-        G::_regulate_frame(self)
+    fn _rule(&self) -> &Rule {
+        G::_rule(self)
+    }
+
+    fn _rule_mut(&mut self) -> &mut Rule {
+        G::_rule_mut(self)
+    }
+
+    fn _topology(&self) -> &Topology {
+        G::_topology(self)
+    }
+
+    fn _topology_mut(&mut self) -> &mut Topology {
+        G::_topology_mut(self)
+    }
+
+    fn _generation(&self) -> u64 {
+        G::_generation(self)
+    }
+
+    fn _generation_mut(&mut self) -> &mut u64 {
+        G::_generation_mut(self)
+    }
+
+    fn _set_next_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        G::_set_next_cell_unchecked(self, point, b)
+    }
+
+    fn _swap_buffers(&mut self) {
+        G::_swap_buffers(self)
+    }
+
+    fn _take_next_cells(&mut self) -> Option<Vec<bool>> {
+        G::_take_next_cells(self)
+    }
+
+    fn _restore_next_cells(&mut self, cells: Vec<bool>) {
+        G::_restore_next_cells(self, cells)
+    }
+
+    fn _box_clone(&self) -> Box<dyn Grid> {
+        G::_box_clone(self)
+    }
+
+    fn _finish_update(&mut self) {
+        G::_finish_update(self)
     }
 
     fn _g_fmt(&self, tc: char, fc: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -596,34 +1049,52 @@ impl<G: GridPrivate> GridPrivate for Box<G> {
     }
 }
 
-impl<G: Grid> Grid for Box<G> {
+impl<G: Grid + ?Sized> Grid for Box<G> {
     fn update(&mut self) {
         G::update(self);
     }
 
+    fn update_with_stats(&mut self) -> GenerationStats {
+        G::update_with_stats(self)
+    }
+
+    fn update_parallel(&mut self, threads: usize) {
+        G::update_parallel(self, threads);
+    }
+
     fn get_cell_unchecked(&self, point: GridPoint) -> bool {
         G::get_cell_unchecked(self, point)
     }
 
-    fn get_cell_unchecked_mut(&mut self, point: GridPoint) -> &mut bool {
-        G::get_cell_unchecked_mut(self, point)
+    fn set_cell_unchecked(&mut self, point: GridPoint, b: bool) {
+        G::set_cell_unchecked(self, point, b)
     }
 }
 
 /*Derived clone causing intermittent stack overflows on windows. Manually derive clone for Box instead.*/
 impl<const WIDTH: usize, const HEIGHT: usize> Clone for Box<Grid2dArr<WIDTH, HEIGHT>> {
     fn clone(&self) -> Self {
-        let mut ret = unsafe {
-            Box::from_raw(alloc::alloc(Layout::new::<Grid2dArr<WIDTH, HEIGHT>>())
-                as *mut Grid2dArr<WIDTH, HEIGHT>)
-        };
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                ret.current_arr[y][x] = self.current_arr[y][x];
-                ret.next_arr[y][x] = self.next_arr[y][x];
-            }
-        }
-        ret.frame_regulator_opt = self.frame_regulator_opt;
-        ret
+        Grid2dArr::boxed_clone(self)
+    }
+}
+
+/*Grids of any backend behind one type, for choosing the backend and size at runtime.*/
+pub type DynGrid = Box<dyn Grid>;
+
+impl Clone for DynGrid {
+    fn clone(&self) -> Self {
+        self._box_clone()
+    }
+}
+
+impl fmt::Display for dyn Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self._g_fmt('\u{2588}', ' ', f)
+    }
+}
+
+impl fmt::Debug for dyn Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self._g_fmt('X', '-', f)
     }
 }
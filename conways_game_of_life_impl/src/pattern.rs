@@ -0,0 +1,196 @@
+use crate::{BResult, GridPoint, GridUnit, OutOfBounds};
+
+/*How Grid::stamp combines a pattern with the cells already in the grid. Overwrite sets
+the whole rectangle covered by the pattern to match it, while the other modes only touch
+the pattern's live cells: Or sets them, Xor toggles them and Clear kills them.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum StampMode {
+    Overwrite,
+    #[default]
+    Or,
+    Xor,
+    Clear,
+}
+
+/*A rectangle of width by height cells, holding the offsets of its live cells from the
+top left corner sorted by row and then column. Transformations return new patterns.*/
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Pattern {
+    size: GridPoint,
+    cells: Vec<GridPoint>,
+}
+
+impl Pattern {
+    pub fn new(size: GridPoint, cells: Vec<GridPoint>) -> BResult<Self> {
+        if let Some(&point) = cells.iter().find(|&&(x, y)| x >= size.0 || y >= size.1) {
+            return Err(OutOfBounds { point, size });
+        }
+        Ok(Self::from_unsorted(size, cells))
+    }
+
+    /*The size is the smallest that holds every cell.*/
+    pub fn from_cells(cells: Vec<GridPoint>) -> Self {
+        let size = cells.iter().fold((0, 0), |(width, height), &(x, y)| {
+            (width.max(x + 1), height.max(y + 1))
+        });
+        Self::from_unsorted(size, cells)
+    }
+
+    fn from_unsorted(size: GridPoint, mut cells: Vec<GridPoint>) -> Self {
+        cells.sort_unstable_by_key(|&(x, y)| (y, x));
+        cells.dedup();
+        Self { size, cells }
+    }
+
+    /*Rows of 'O' for live cells and '.' for dead ones.*/
+    fn from_rows(rows: &[&str]) -> Self {
+        let mut cells = Vec::new();
+        for (row, y) in rows.iter().zip(0..) {
+            for (c, x) in row.chars().zip(0..) {
+                if c == 'O' {
+                    cells.push((x, y));
+                }
+            }
+        }
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        Self::from_unsorted((width as GridUnit, rows.len() as GridUnit), cells)
+    }
+
+    pub fn size(&self) -> GridPoint {
+        self.size
+    }
+
+    pub fn cells(&self) -> &[GridPoint] {
+        &self.cells
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn get_cell(&self, (x, y): GridPoint) -> bool {
+        self.cells
+            .binary_search_by_key(&(y, x), |&(x, y)| (y, x))
+            .is_ok()
+    }
+
+    fn map_cells<F: Fn(GridPoint) -> GridPoint>(&self, size: GridPoint, f: F) -> Self {
+        Self::from_unsorted(size, self.cells.iter().map(|&point| f(point)).collect())
+    }
+
+    /*Rotations are clockwise.*/
+    pub fn rotate_90(&self) -> Self {
+        let (width, height) = self.size;
+        self.map_cells((height, width), |(x, y)| (height - 1 - y, x))
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        let (width, height) = self.size;
+        self.map_cells(self.size, |(x, y)| (width - 1 - x, height - 1 - y))
+    }
+
+    pub fn rotate_270(&self) -> Self {
+        let (width, height) = self.size;
+        self.map_cells((height, width), |(x, y)| (y, width - 1 - x))
+    }
+
+    /*Mirrors left to right.*/
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.size.0;
+        self.map_cells(self.size, |(x, y)| (width - 1 - x, y))
+    }
+
+    /*Mirrors top to bottom.*/
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.size.1;
+        self.map_cells(self.size, |(x, y)| (x, height - 1 - y))
+    }
+
+    /*Moves the cells right and down, growing the pattern to keep them inside it. None if
+    the new size does not fit in a GridUnit.*/
+    pub fn translate(&self, (dx, dy): GridPoint) -> Option<Self> {
+        let size = (self.size.0.checked_add(dx)?, self.size.1.checked_add(dy)?);
+        Some(self.map_cells(size, |(x, y)| (x + dx, y + dy)))
+    }
+
+    /*Cells live in either pattern, in a rectangle big enough for both.*/
+    pub fn union(&self, other: &Self) -> Self {
+        let size = (self.size.0.max(other.size.0), self.size.1.max(other.size.1));
+        let cells = self.cells.iter().chain(&other.cells).copied().collect();
+        Self::from_unsorted(size, cells)
+    }
+
+    pub fn block() -> Self {
+        Self::from_rows(&["OO", "OO"])
+    }
+
+    pub fn bee_hive() -> Self {
+        Self::from_rows(&[".OO.", "O..O", ".OO."])
+    }
+
+    pub fn loaf() -> Self {
+        Self::from_rows(&[".OO.", "O..O", ".O.O", "..O."])
+    }
+
+    pub fn boat() -> Self {
+        Self::from_rows(&["OO.", "O.O", ".O."])
+    }
+
+    pub fn tub() -> Self {
+        Self::from_rows(&[".O.", "O.O", ".O."])
+    }
+
+    pub fn blinker() -> Self {
+        Self::from_rows(&["OOO"])
+    }
+
+    pub fn toad() -> Self {
+        Self::from_rows(&[".OOO", "OOO."])
+    }
+
+    pub fn beacon() -> Self {
+        Self::from_rows(&["OO..", "OO..", "..OO", "..OO"])
+    }
+
+    pub fn pulsar() -> Self {
+        Self::from_rows(&[
+            "..OOO...OOO..",
+            ".............",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            "..OOO...OOO..",
+            ".............",
+            "..OOO...OOO..",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            ".............",
+            "..OOO...OOO..",
+        ])
+    }
+
+    pub fn penta_decathlon() -> Self {
+        Self::from_rows(&[
+            ".O.", ".O.", "O.O", ".O.", ".O.", ".O.", ".O.", "O.O", ".O.", ".O.",
+        ])
+    }
+
+    /*Heads down and to the right; rotate it to send it in the other three directions.*/
+    pub fn glider() -> Self {
+        Self::from_rows(&[".O.", "..O", "OOO"])
+    }
+
+    /*The spaceships head to the left.*/
+    pub fn lwss() -> Self {
+        Self::from_rows(&[".O..O", "O....", "O...O", "OOOO."])
+    }
+
+    pub fn mwss() -> Self {
+        Self::from_rows(&["...O..", ".O...O", "O.....", "O....O", "OOOOO."])
+    }
+
+    pub fn hwss() -> Self {
+        Self::from_rows(&["...OO..", ".O....O", "O......", "O.....O", "OOOOOO."])
+    }
+}
@@ -0,0 +1,137 @@
+use std::{error::Error, fmt, str::FromStr};
+
+type NeighbourMask = u16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: NeighbourMask,
+    survival: NeighbourMask,
+}
+
+#[derive(Debug)]
+pub enum RuleParseError {
+    MissingSeparator,
+    InvalidCharacter(char),
+    InvalidNeighbourCount(u8),
+    RepeatedNeighbourCount(u8),
+    MixedNotation,
+}
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl Error for RuleParseError {}
+
+impl Rule {
+    pub const CONWAY: Self = Self {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    pub fn new(birth: &[u8], survival: &[u8]) -> Result<Self, RuleParseError> {
+        fn get_mask(counts: &[u8]) -> Result<NeighbourMask, RuleParseError> {
+            counts.iter().try_fold(0, |mask, &count| {
+                if count > 8 {
+                    Err(RuleParseError::InvalidNeighbourCount(count))
+                } else if mask >> count & 1 == 1 {
+                    Err(RuleParseError::RepeatedNeighbourCount(count))
+                } else {
+                    Ok(mask | 1 << count)
+                }
+            })
+        }
+
+        Ok(Self {
+            birth: get_mask(birth)?,
+            survival: get_mask(survival)?,
+        })
+    }
+
+    pub fn birth(&self, neighbours: u8) -> bool {
+        self.birth >> neighbours & 1 == 1
+    }
+
+    pub fn survival(&self, neighbours: u8) -> bool {
+        self.survival >> neighbours & 1 == 1
+    }
+
+    pub fn next_state(&self, alive: bool, neighbours: u8) -> bool {
+        if alive {
+            self.survival(neighbours)
+        } else {
+            self.birth(neighbours)
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::CONWAY
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleParseError;
+
+    /*Accepts B/S notation (B36/S23, S23/B36, case insensitive) and the older S/B
+    notation without prefixes (23/36). A count may appear only once in each half.*/
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_counts(part: &str) -> Result<NeighbourMask, RuleParseError> {
+            part.chars()
+                .try_fold(0, |mask: NeighbourMask, c| match c.to_digit(10) {
+                    Some(count) if count > 8 => Err(RuleParseError::InvalidCharacter(c)),
+                    Some(count) if mask >> count & 1 == 1 => {
+                        Err(RuleParseError::RepeatedNeighbourCount(count as u8))
+                    }
+                    Some(count) => Ok(mask | 1 << count),
+                    None => Err(RuleParseError::InvalidCharacter(c)),
+                })
+        }
+
+        fn split_prefix(part: &str) -> (Option<bool>, &str) {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some('B') | Some('b') => (Some(true), chars.as_str()),
+                Some('S') | Some('s') => (Some(false), chars.as_str()),
+                _ => (None, part),
+            }
+        }
+
+        let mut parts = s.trim().splitn(2, '/');
+        let first = parts.next().unwrap_or_default();
+        let second = parts.next().ok_or(RuleParseError::MissingSeparator)?;
+
+        let (birth, survival) = match (split_prefix(first), split_prefix(second)) {
+            ((Some(true), birth), (Some(false), survival))
+            | ((Some(false), survival), (Some(true), birth)) => (birth, survival),
+            ((None, survival), (None, birth)) => (birth, survival),
+            _ => return Err(RuleParseError::MixedNotation),
+        };
+
+        Ok(Self {
+            birth: parse_counts(birth)?,
+            survival: parse_counts(survival)?,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for count in (0..=8).filter(|&count| self.birth(count)) {
+            write!(f, "{}", count)?;
+        }
+        write!(f, "/S")?;
+        for count in (0..=8).filter(|&count| self.survival(count)) {
+            write!(f, "{}", count)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rule({})", self)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{FrameRegulator, Grid};
+use std::time::{Duration, Instant};
+
+const RATE_WINDOW: Duration = Duration::from_millis(500);
+/*The longest a frame is made to last, however slow the target.*/
+const MAX_FRAME_DURATION: Duration = Duration::from_secs(60);
+
+/*Runs a grid at a target number of generations per second. Each frame advances
+2^step_exponent generations, as in Golly, and the frames are spaced so the generations
+add up to gps times speed per second. A gps of 0 runs as fast as possible.*/
+pub struct Simulation<G: Grid> {
+    grid: G,
+    generations: u64,
+    gps: u64,
+    speed: f64,
+    step_exponent: u32,
+    frame_regulator_opt: Option<FrameRegulator>,
+    window_start: Instant,
+    window_generations: u64,
+    measured_gps: Option<f64>,
+}
+
+impl<G: Grid> Simulation<G> {
+    /*Frames step the grid one generation at a time, so past 2^20 generations a frame
+    takes long enough on any grid that the simulation stops responding.*/
+    pub const MAX_STEP_EXPONENT: u32 = 20;
+
+    pub fn new(grid: G) -> Self {
+        Self {
+            grid,
+            generations: 0,
+            gps: 0,
+            speed: 1.,
+            step_exponent: 0,
+            frame_regulator_opt: None,
+            window_start: Instant::now(),
+            window_generations: 0,
+            measured_gps: None,
+        }
+    }
+
+    pub fn grid(&self) -> &G {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut G {
+        &mut self.grid
+    }
+
+    pub fn into_grid(self) -> G {
+        self.grid
+    }
+
+    /*Generations run by this simulation, as opposed to the grid's own generation. Stops
+    at u64::MAX rather than wrapping.*/
+    pub fn generations(&self) -> u64 {
+        self.generations
+    }
+
+    pub fn gps(&self) -> u64 {
+        self.gps
+    }
+
+    pub fn set_gps(&mut self, gps: u64) {
+        self.gps = gps;
+        self.reset_frame_regulator();
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /*Multiplies the gps. Speeds that are not positive are treated as the smallest
+    positive speed. However small the speed, a frame lasts at most a minute.*/
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = if speed > 0. { speed } else { f64::MIN_POSITIVE };
+        self.reset_frame_regulator();
+    }
+
+    pub fn step_exponent(&self) -> u32 {
+        self.step_exponent
+    }
+
+    /*Exponents past MAX_STEP_EXPONENT are clamped to it.*/
+    pub fn set_step_exponent(&mut self, step_exponent: u32) {
+        self.step_exponent = step_exponent.min(Self::MAX_STEP_EXPONENT);
+        self.reset_frame_regulator();
+    }
+
+    pub fn generations_per_frame(&self) -> u64 {
+        1 << self.step_exponent
+    }
+
+    pub fn target_gps(&self) -> Option<f64> {
+        if self.gps != 0 {
+            Some(self.gps as f64 * self.speed)
+        } else {
+            None
+        }
+    }
+
+    /*Generations per second over the last half second or so, None until the first half
+    second has passed.*/
+    pub fn measured_gps(&self) -> Option<f64> {
+        self.measured_gps
+    }
+
+    /*Advances the grid by one frame's worth of generations, then sleeps for the rest of
+    the frame. Returns the generations run.*/
+    pub fn frame(&mut self) -> u64 {
+        let generations = self.generations_per_frame();
+        self.grid.step(generations);
+        self.generations = self.generations.saturating_add(generations);
+        if let Some(frame_regulator) = &mut self.frame_regulator_opt {
+            frame_regulator.regulate();
+        }
+
+        self.window_generations = self.window_generations.saturating_add(generations);
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= RATE_WINDOW {
+            self.measured_gps = Some(self.window_generations as f64 / elapsed.as_secs_f64());
+            self.window_start = Instant::now();
+            self.window_generations = 0;
+        }
+        generations
+    }
+
+    fn reset_frame_regulator(&mut self) {
+        self.frame_regulator_opt = self.target_gps().map(|target_gps| {
+            let seconds = self.generations_per_frame() as f64 / target_gps;
+            FrameRegulator::new(
+                Duration::try_from_secs_f64(seconds).map_or(MAX_FRAME_DURATION, |duration| {
+                    duration.min(MAX_FRAME_DURATION)
+                }),
+            )
+        });
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{check_region, Grid, GridPoint, OutOfBounds};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{error::Error, fmt};
+
+/*Symmetries of the soup's rectangle, named as in census searches. C2 and C4 are rotations
+by 180 and 90 degrees; D2 mirrors left to right, D4 adds top to bottom and D8 adds the
+diagonals. C4 and D8 need a square rectangle.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Symmetry {
+    #[default]
+    C1,
+    C2,
+    C4,
+    D2,
+    D4,
+    D8,
+}
+
+impl Symmetry {
+    fn needs_square(&self) -> bool {
+        matches!(self, Self::C4 | Self::D8)
+    }
+
+    /*Images of a point under every element of the group, in the rectangle's coordinates.*/
+    fn orbit(
+        &self,
+        (x, y): GridPoint,
+        (width, height): GridPoint,
+    ) -> impl Iterator<Item = GridPoint> {
+        let (x_mirror, y_mirror) = (width - 1 - x, height - 1 - y);
+        /*The identity, the three rotations, then the four reflections.*/
+        let images = [
+            (x, y),
+            (y_mirror, x),
+            (x_mirror, y_mirror),
+            (y, x_mirror),
+            (x_mirror, y),
+            (x, y_mirror),
+            (y, x),
+            (y_mirror, x_mirror),
+        ];
+        let elements: &'static [usize] = match self {
+            Self::C1 => &[0],
+            Self::C2 => &[0, 2],
+            Self::C4 => &[0, 1, 2, 3],
+            Self::D2 => &[0, 4],
+            Self::D4 => &[0, 2, 4, 5],
+            Self::D8 => &[0, 1, 2, 3, 4, 5, 6, 7],
+        };
+        elements.iter().map(move |&i| images[i])
+    }
+}
+
+/*Chance of each cell being alive. Clustered fills discs of the given radius around count
+random centres, fading linearly from density at the centre to nothing at the edge. Noise
+varies smoothly between min and max over features roughly scale cells across.*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Density {
+    Uniform(f64),
+    Clustered {
+        count: usize,
+        radius: f64,
+        density: f64,
+    },
+    Noise {
+        scale: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl Default for Density {
+    fn default() -> Self {
+        Self::Uniform(0.5)
+    }
+}
+
+enum Field {
+    Uniform(f64),
+    Clustered {
+        centres: Vec<(f64, f64)>,
+        radius: f64,
+        density: f64,
+    },
+    Noise {
+        lattice: Vec<Vec<f64>>,
+        scale: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl Field {
+    /*Draws whatever the density needs up front, so cell draws follow in a fixed order.*/
+    fn new(density: Density, (width, height): GridPoint, rng: &mut StdRng) -> Self {
+        match density {
+            Density::Uniform(density) => Self::Uniform(density),
+            Density::Clustered {
+                count,
+                radius,
+                density,
+            } => Self::Clustered {
+                centres: (0..count)
+                    .map(|_| {
+                        (
+                            rng.gen::<f64>() * width as f64,
+                            rng.gen::<f64>() * height as f64,
+                        )
+                    })
+                    .collect(),
+                radius: radius.max(f64::MIN_POSITIVE),
+                density,
+            },
+            Density::Noise { scale, min, max } => {
+                let scale = scale.max(1.);
+                let lattice_size = |length| (length as f64 / scale) as usize + 2;
+                Self::Noise {
+                    lattice: (0..lattice_size(height))
+                        .map(|_| (0..lattice_size(width)).map(|_| rng.gen()).collect())
+                        .collect(),
+                    scale,
+                    min,
+                    max,
+                }
+            }
+        }
+    }
+
+    fn density(&self, (x, y): GridPoint) -> f64 {
+        let (x, y) = (x as f64 + 0.5, y as f64 + 0.5);
+        match self {
+            Self::Uniform(density) => *density,
+            Self::Clustered {
+                centres,
+                radius,
+                density,
+            } => centres
+                .iter()
+                .map(|&(c_x, c_y)| {
+                    let distance = ((x - c_x).powi(2) + (y - c_y).powi(2)).sqrt();
+                    density * (1. - distance / radius).max(0.)
+                })
+                .fold(0., f64::max),
+            Self::Noise {
+                lattice,
+                scale,
+                min,
+                max,
+            } => {
+                let (l_x, l_y) = (x / scale, y / scale);
+                let (i, j) = (l_x as usize, l_y as usize);
+                let smooth = |t: f64| t * t * (3. - 2. * t);
+                let (t_x, t_y) = (smooth(l_x.fract()), smooth(l_y.fract()));
+                let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+                let top = lerp(lattice[j][i], lattice[j][i + 1], t_x);
+                let bottom = lerp(lattice[j + 1][i], lattice[j + 1][i + 1], t_x);
+                lerp(*min, *max, lerp(top, bottom, t_y))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SoupError {
+    OutOfBounds(OutOfBounds),
+    NotSquare(Symmetry, GridPoint),
+}
+
+impl fmt::Display for SoupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for SoupError {}
+
+impl From<OutOfBounds> for SoupError {
+    fn from(e: OutOfBounds) -> Self {
+        Self::OutOfBounds(e)
+    }
+}
+
+/*Random soup generator. Without an explicit seed a fresh one is drawn for every fill, and
+without a region the whole grid is filled. Filling overwrites every cell of the region.*/
+#[derive(Clone, Debug, Default)]
+pub struct Soup {
+    seed: Option<u64>,
+    region: Option<(GridPoint, GridPoint)>,
+    symmetry: Symmetry,
+    density: Density,
+}
+
+impl Soup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn region(mut self, top_left: GridPoint, size: GridPoint) -> Self {
+        self.region = Some((top_left, size));
+        self
+    }
+
+    pub fn symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /*Returns the seed used, which reproduces the soup when passed back to seed.*/
+    pub fn fill<G: Grid>(&self, grid: &mut G) -> Result<u64, SoupError> {
+        let ((x, y), size) = self.region.unwrap_or(((0, 0), grid.size()));
+        check_region(grid, (x, y), size)?;
+        if self.symmetry.needs_square() && size.0 != size.1 {
+            return Err(SoupError::NotSquare(self.symmetry, size));
+        }
+
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let field = Field::new(self.density, size, &mut rng);
+        /*Cells are visited row by row, so the first point of each orbit in that order is
+        visited before the rest and decides the state of the whole orbit.*/
+        for y_offset in 0..size.1 {
+            for x_offset in 0..size.0 {
+                let point = (x_offset, y_offset);
+                let first = self
+                    .symmetry
+                    .orbit(point, size)
+                    .min_by_key(|&(x, y)| (y, x))
+                    .unwrap_or(point);
+                let b = if first == point {
+                    rng.gen::<f64>() < field.density(point)
+                } else {
+                    grid.get_cell_unchecked((x + first.0, y + first.1))
+                };
+                grid.set_cell_unchecked((x + x_offset, y + y_offset), b);
+            }
+        }
+        Ok(seed)
+    }
+}
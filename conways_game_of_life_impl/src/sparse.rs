@@ -0,0 +1,197 @@
+use crate::{
+    for_each_line_point, Grid, GridUnit, Rule, SetLineOpt, UniversePoint, UniverseUnit,
+    UnsupportedRule,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    fmt,
+};
+
+/*Unbounded universe that stores only its live cells. Each update counts neighbours over
+the live cells and their frontier, so the cost follows the population rather than the
+area. Rules with B0 are rejected, as empty space would not stay empty.*/
+#[derive(Clone)]
+pub struct SparseUniverse {
+    live: HashSet<UniversePoint>,
+    rule: Rule,
+    generation: u64,
+}
+
+impl SparseUniverse {
+    pub fn empty() -> Self {
+        Self {
+            live: HashSet::new(),
+            rule: Rule::default(),
+            generation: 0,
+        }
+    }
+
+    pub fn from_grid<G: Grid>(grid: &G) -> Result<Self, UnsupportedRule> {
+        let mut ret = Self::empty();
+        ret.set_rule(grid.rule())?;
+        grid.inspect(|(x, y), grid| {
+            if grid.get_cell_unchecked((x, y)) {
+                ret.live.insert((x as UniverseUnit, y as UniverseUnit));
+            }
+        });
+        Ok(ret)
+    }
+
+    /*Overwrites every cell of the grid with the universe window whose top left corner is
+    at top_left.*/
+    pub fn write_to_grid<G: Grid>(&self, grid: &mut G, top_left: UniversePoint) {
+        let size = grid.size();
+        grid.inspect_mut(|point, grid| grid.set_cell_unchecked(point, false));
+        for &(x, y) in &self.live {
+            let (x, y) = (x.wrapping_sub(top_left.0), y.wrapping_sub(top_left.1));
+            if (0..size.0 as UniverseUnit).contains(&x) && (0..size.1 as UniverseUnit).contains(&y)
+            {
+                grid.set_cell_unchecked((x as GridUnit, y as GridUnit), true);
+            }
+        }
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) -> Result<(), UnsupportedRule> {
+        if rule.birth(0) {
+            return Err(UnsupportedRule::new(rule));
+        }
+        self.rule = rule;
+        Ok(())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn live_cells(&self) -> impl Iterator<Item = UniversePoint> + '_ {
+        self.live.iter().copied()
+    }
+
+    pub fn bounding_box(&self) -> Option<(UniversePoint, UniversePoint)> {
+        self.live.iter().fold(None, |bounding_box, &(x, y)| {
+            Some(match bounding_box {
+                Some(((x_min, y_min), (x_max, y_max))) => (
+                    (UniverseUnit::min(x_min, x), UniverseUnit::min(y_min, y)),
+                    (UniverseUnit::max(x_max, x), UniverseUnit::max(y_max, y)),
+                ),
+                None => ((x, y), (x, y)),
+            })
+        })
+    }
+
+    pub fn update(&mut self) {
+        let mut counters: HashMap<UniversePoint, u8> = HashMap::with_capacity(self.live.len() * 4);
+        for &(x, y) in &self.live {
+            for y_offset in -1..=1 {
+                for x_offset in -1..=1 {
+                    if x_offset != 0 || y_offset != 0 {
+                        let point = (x.wrapping_add(x_offset), y.wrapping_add(y_offset));
+                        *counters.entry(point).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        let mut next: HashSet<UniversePoint> = counters
+            .iter()
+            .filter(|&(point, &counter)| self.rule.next_state(self.live.contains(point), counter))
+            .map(|(&point, _)| point)
+            .collect();
+        if self.rule.survival(0) {
+            next.extend(
+                self.live
+                    .iter()
+                    .filter(|point| !counters.contains_key(point)),
+            );
+        }
+
+        self.live = next;
+        self.generation += 1;
+    }
+
+    pub fn get_cell(&self, point: UniversePoint) -> bool {
+        self.live.contains(&point)
+    }
+
+    pub fn set_cell(&mut self, point: UniversePoint, b: bool) {
+        if b {
+            self.live.insert(point);
+        } else {
+            self.live.remove(&point);
+        }
+    }
+
+    pub fn set_hline(
+        &mut self,
+        start: UniverseUnit,
+        length: UniverseUnit,
+        y: UniverseUnit,
+        b: bool,
+    ) {
+        self.set_aaline(start, length, y, b, SetLineOpt::Horizontal);
+    }
+
+    pub fn set_vline(
+        &mut self,
+        start: UniverseUnit,
+        length: UniverseUnit,
+        x: UniverseUnit,
+        b: bool,
+    ) {
+        self.set_aaline(start, length, x, b, SetLineOpt::Vertical);
+    }
+
+    pub fn set_aaline(
+        &mut self,
+        start: UniverseUnit,
+        length: UniverseUnit,
+        other: UniverseUnit,
+        b: bool,
+        opt: SetLineOpt,
+    ) {
+        let mut i = start;
+        for _ in 0..length.max(0) {
+            let point = match opt {
+                SetLineOpt::Horizontal => (i, other),
+                SetLineOpt::Vertical => (other, i),
+            };
+            self.set_cell(point, b);
+            i = i.wrapping_add(1);
+        }
+    }
+
+    pub fn set_line(&mut self, start: UniversePoint, end: UniversePoint, b: bool) {
+        let Ok(()) = for_each_line_point(start, end, |point| -> Result<(), Infallible> {
+            self.set_cell(point, b);
+            Ok(())
+        });
+    }
+}
+
+impl Default for SparseUniverse {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Debug for SparseUniverse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SparseUniverse {{ rule: {}, generation: {}, population: {}, bounding_box: {:?} }}",
+            self.rule,
+            self.generation,
+            self.population(),
+            self.bounding_box()
+        )
+    }
+}
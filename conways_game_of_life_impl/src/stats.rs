@@ -0,0 +1,173 @@
+use crate::{Grid, GridPoint, GridUnit};
+use std::io::{self, Write};
+
+/*Counts for the generation just computed. Births and deaths compare it with the one
+before, and the bounding box holds the top left and bottom right live cells.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GenerationStats {
+    generation: u64,
+    population: u64,
+    births: u64,
+    deaths: u64,
+    bounding_box: Option<(GridPoint, GridPoint)>,
+}
+impl GenerationStats {
+    /*Stats of the grid as it is, with no births or deaths, such as for generation 0.*/
+    pub fn from_grid<G: Grid>(grid: &G) -> Self {
+        let mut counter = StatsCounter::default();
+        grid.inspect(|point, grid| {
+            let alive = grid.get_cell_unchecked(point);
+            counter.count_cell(point, alive, alive);
+        });
+        counter.finish(grid.generation())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    pub fn population(&self) -> u64 {
+        self.population
+    }
+    pub fn births(&self) -> u64 {
+        self.births
+    }
+    pub fn deaths(&self) -> u64 {
+        self.deaths
+    }
+    pub fn bounding_box(&self) -> Option<(GridPoint, GridPoint)> {
+        self.bounding_box
+    }
+}
+
+/*Accumulates stats cell by cell, or 64 cells at a time for bit-packed rows, while the
+next generation is computed. Counting is kept free of branches, as cell states in a soup
+are too random to predict. The bounding box is only meaningful while population > 0.*/
+#[derive(Clone, Copy)]
+pub(crate) struct StatsCounter {
+    population: u64,
+    births: u64,
+    deaths: u64,
+    min: GridPoint,
+    max: GridPoint,
+}
+
+impl Default for StatsCounter {
+    fn default() -> Self {
+        Self {
+            population: 0,
+            births: 0,
+            deaths: 0,
+            min: (GridUnit::MAX, GridUnit::MAX),
+            max: (0, 0),
+        }
+    }
+}
+
+impl StatsCounter {
+    pub(crate) fn count_cell(&mut self, (x, y): GridPoint, alive: bool, next: bool) {
+        self.population += next as u64;
+        self.births += (next && !alive) as u64;
+        self.deaths += (alive && !next) as u64;
+        let live = |a: GridUnit, b: GridUnit| if next { a } else { b };
+        self.min = (
+            live(x, GridUnit::MAX).min(self.min.0),
+            live(y, GridUnit::MAX).min(self.min.1),
+        );
+        self.max = (live(x, 0).max(self.max.0), live(y, 0).max(self.max.1));
+    }
+
+    /*Bit i of the words is the cell i columns right of first.*/
+    pub(crate) fn count_word(&mut self, (x, y): GridPoint, alive: u64, next: u64) {
+        self.population += next.count_ones() as u64;
+        self.births += (next & !alive).count_ones() as u64;
+        self.deaths += (alive & !next).count_ones() as u64;
+        if next != 0 {
+            self.expand(
+                (x + next.trailing_zeros() as GridUnit, y),
+                (x + 63 - next.leading_zeros() as GridUnit, y),
+            );
+        }
+    }
+
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.population += other.population;
+        self.births += other.births;
+        self.deaths += other.deaths;
+        self.expand(other.min, other.max);
+    }
+
+    pub(crate) fn finish(&self, generation: u64) -> GenerationStats {
+        GenerationStats {
+            generation,
+            population: self.population,
+            births: self.births,
+            deaths: self.deaths,
+            bounding_box: if self.population > 0 {
+                Some((self.min, self.max))
+            } else {
+                None
+            },
+        }
+    }
+
+    fn expand(&mut self, min: GridPoint, max: GridPoint) {
+        self.min = (self.min.0.min(min.0), self.min.1.min(min.1));
+        self.max = (self.max.0.max(max.0), self.max.1.max(max.1));
+    }
+}
+
+/*Time series of generation stats, in the order they were produced.*/
+#[derive(Clone, Debug, Default)]
+pub struct StatsSeries {
+    stats: Vec<GenerationStats>,
+}
+
+impl StatsSeries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /*Updates the grid and appends the stats of the new generation.*/
+    pub fn update<G: Grid>(&mut self, grid: &mut G) -> GenerationStats {
+        let stats = grid.update_with_stats();
+        self.stats.push(stats);
+        stats
+    }
+
+    pub fn push(&mut self, stats: GenerationStats) {
+        self.stats.push(stats);
+    }
+
+    pub fn as_slice(&self) -> &[GenerationStats] {
+        &self.stats
+    }
+
+    pub fn last(&self) -> Option<&GenerationStats> {
+        self.stats.last()
+    }
+
+    pub fn clear(&mut self) {
+        self.stats.clear();
+    }
+
+    /*One header line, then one line per generation. Empty bounding boxes leave their
+    columns blank.*/
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(
+            writer,
+            "generation,population,births,deaths,min_x,min_y,max_x,max_y"
+        )?;
+        for stats in &self.stats {
+            write!(
+                writer,
+                "{},{},{},{},",
+                stats.generation, stats.population, stats.births, stats.deaths
+            )?;
+            match stats.bounding_box {
+                Some((min, max)) => writeln!(writer, "{},{},{},{}", min.0, min.1, max.0, max.1)?,
+                None => writeln!(writer, ",,,")?,
+            }
+        }
+        Ok(())
+    }
+}
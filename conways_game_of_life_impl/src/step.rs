@@ -0,0 +1,138 @@
+use crate::{Grid, GridPoint, History, Repeat};
+
+/*Checked after every generation. PopulationCrosses stops on the generation whose
+population is on the other side of the threshold from the one before it, counting a
+population equal to the threshold as above it. Points of a region outside the grid are
+ignored.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum StopCondition {
+    Extinct,
+    Periodic,
+    PopulationCrosses(u64),
+    CellChanged(GridPoint),
+    RegionChanged(GridPoint, GridPoint),
+}
+
+/*Completed when all the generations asked for ran. Otherwise the condition that held,
+with the repeat found for Periodic, whose generations count from the start of the step,
+and the threshold and new population for PopulationCrosses.*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum StopReason {
+    Completed,
+    Extinct,
+    Periodic(Repeat),
+    PopulationCrosses(u64, u64),
+    CellChanged(GridPoint),
+    RegionChanged(GridPoint, GridPoint),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Stop {
+    reason: StopReason,
+    generation: u64,
+    steps: u64,
+}
+impl Stop {
+    pub fn reason(&self) -> StopReason {
+        self.reason
+    }
+    /*The grid's generation when it stopped.*/
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+}
+
+fn region_cells<G: Grid>(grid: &G, (x, y): GridPoint, (width, height): GridPoint) -> Vec<bool> {
+    (0..height)
+        .flat_map(|y_offset| (0..width).map(move |x_offset| (x_offset, y_offset)))
+        .map(
+            |(x_offset, y_offset)| match (x.checked_add(x_offset), y.checked_add(y_offset)) {
+                (Some(x), Some(y)) => grid.get_cell((x, y)),
+                _ => false,
+            },
+        )
+        .collect()
+}
+
+pub(crate) fn step_until<G: Grid>(grid: &mut G, n: u64, conditions: &[StopCondition]) -> Stop {
+    let counts_population = conditions.iter().any(|condition| {
+        matches!(
+            condition,
+            StopCondition::Extinct | StopCondition::PopulationCrosses(_)
+        )
+    });
+    let mut history = if conditions.contains(&StopCondition::Periodic) {
+        let mut history = History::new();
+        history.record(grid);
+        Some(history)
+    } else {
+        None
+    };
+    let region = |condition: &StopCondition| match *condition {
+        StopCondition::CellChanged(point) => Some((point, (1, 1))),
+        StopCondition::RegionChanged(top_left, size) => Some((top_left, size)),
+        _ => None,
+    };
+    let mut regions: Vec<_> = conditions
+        .iter()
+        .map(|condition| {
+            region(condition).map(|(top_left, size)| region_cells(grid, top_left, size))
+        })
+        .collect();
+    let mut population = counts_population.then(|| {
+        let mut population = 0;
+        grid.inspect(|point, grid| population += grid.get_cell_unchecked(point) as u64);
+        population
+    });
+
+    let stop = |grid: &G, reason, steps| Stop {
+        reason,
+        generation: grid.generation(),
+        steps,
+    };
+    let mut result = None;
+    for steps in 1..=n {
+        let new_population = if counts_population {
+            Some(grid.update_with_stats().population())
+        } else {
+            grid.update();
+            None
+        };
+        let repeat = history.as_mut().and_then(|history| history.record(grid));
+
+        let reason = conditions
+            .iter()
+            .zip(&mut regions)
+            .find_map(|(condition, cells)| match *condition {
+                StopCondition::Extinct => {
+                    (new_population == Some(0)).then_some(StopReason::Extinct)
+                }
+                StopCondition::Periodic => repeat.map(StopReason::Periodic),
+                StopCondition::PopulationCrosses(threshold) => match (population, new_population) {
+                    (Some(old), Some(new)) if (old >= threshold) != (new >= threshold) => {
+                        Some(StopReason::PopulationCrosses(threshold, new))
+                    }
+                    _ => None,
+                },
+                StopCondition::CellChanged(_) | StopCondition::RegionChanged(..) => {
+                    let (top_left, size) = region(condition)?;
+                    let new_cells = region_cells(grid, top_left, size);
+                    let changed = cells.as_ref() != Some(&new_cells);
+                    *cells = Some(new_cells);
+                    changed.then_some(match *condition {
+                        StopCondition::CellChanged(point) => StopReason::CellChanged(point),
+                        _ => StopReason::RegionChanged(top_left, size),
+                    })
+                }
+            });
+        population = new_population;
+        if let Some(reason) = reason {
+            result = Some(stop(grid, reason, steps));
+            break;
+        }
+    }
+    result.unwrap_or_else(|| stop(grid, StopReason::Completed, n))
+}
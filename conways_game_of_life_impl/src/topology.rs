@@ -0,0 +1,52 @@
+use crate::{GridPoint, GridUnit};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Topology {
+    #[default]
+    Torus,
+    Plane,
+    KleinBottle,
+    CrossSurface,
+}
+
+impl Topology {
+    /*Maps a point that may lie just outside the grid onto the cell it is glued to, or None
+    if the point is permanently dead. Klein bottle edges mirror x when crossing the top or
+    bottom; cross-surface edges additionally mirror y when crossing the left or right.*/
+    pub fn map_point(&self, (mut x, mut y): (i32, i32), size: GridPoint) -> Option<GridPoint> {
+        let width = size.0 as i32;
+        let height = size.1 as i32;
+        let x_crossed = x < 0 || x >= width;
+        let y_crossed = y < 0 || y >= height;
+
+        match self {
+            Self::Torus => (),
+            Self::Plane => {
+                if x_crossed || y_crossed {
+                    return None;
+                }
+            }
+            Self::KleinBottle => {
+                if y_crossed {
+                    x = width - 1 - x;
+                }
+            }
+            Self::CrossSurface => {
+                if x_crossed {
+                    y = height - 1 - y;
+                }
+                if y_crossed {
+                    x = width - 1 - x;
+                }
+            }
+        }
+
+        if x_crossed {
+            x = x.rem_euclid(width);
+        }
+        if y_crossed {
+            y = y.rem_euclid(height);
+        }
+        Some((x as GridUnit, y as GridUnit))
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{Grid, GridPoint, GridUnit};
+use std::{collections::VecDeque, mem};
+
+const DEFAULT_MEMORY_LIMIT: usize = 64 << 20;
+
+/*The cells a change flipped: their indices in row-major order while few flipped,
+otherwise a bit mask over the whole grid, whichever is smaller.*/
+enum Flips {
+    Sparse(Vec<u32>),
+    Dense(Vec<u64>),
+}
+
+struct Delta {
+    flips: Flips,
+    generations: (u64, u64),
+}
+
+impl Delta {
+    fn memory(&self) -> usize {
+        mem::size_of::<Self>()
+            + match &self.flips {
+                Flips::Sparse(indices) => indices.len() * mem::size_of::<u32>(),
+                Flips::Dense(words) => words.len() * mem::size_of::<u64>(),
+            }
+    }
+
+    fn apply<G: Grid>(&self, grid: &mut G, generation: u64) {
+        let width = grid.size().0 as usize;
+        let mut flip = |index: usize| {
+            let point = ((index % width) as GridUnit, (index / width) as GridUnit);
+            let b = grid.get_cell_unchecked(point);
+            grid.set_cell_unchecked(point, !b);
+        };
+        match &self.flips {
+            Flips::Sparse(indices) => indices.iter().for_each(|&index| flip(index as usize)),
+            Flips::Dense(words) => {
+                for (i, &word) in words.iter().enumerate() {
+                    let mut word = word;
+                    while word != 0 {
+                        flip(i * 64 + word.trailing_zeros() as usize);
+                        word &= word - 1;
+                    }
+                }
+            }
+        }
+        grid.set_generation(generation);
+    }
+}
+
+struct Snapshot {
+    size: GridPoint,
+    generation: u64,
+    words: Vec<u64>,
+}
+
+impl Snapshot {
+    fn new<G: Grid>(grid: &G) -> Self {
+        let size = grid.size();
+        let mut words = vec![0; (size.0 as usize * size.1 as usize).div_ceil(64)];
+        grid.inspect(|(x, y), grid| {
+            if grid.get_cell_unchecked((x, y)) {
+                let index = y as usize * size.0 as usize + x as usize;
+                words[index / 64] |= 1 << (index % 64);
+            }
+        });
+        Self {
+            size,
+            generation: grid.generation(),
+            words,
+        }
+    }
+}
+
+/*Undo and redo for any change to a grid, be it edits or updates. Call begin before the
+change and commit after it; everything in between becomes one step. Steps store only the
+cells that flipped, and the oldest are dropped once they take more than the memory limit.
+Changes to the rule or topology are not recorded. The grid must only change through the
+history or between begin and commit, as each step starts from where the last one left it.*/
+pub struct UndoHistory {
+    undo: VecDeque<Delta>,
+    redo: Vec<Delta>,
+    memory_limit: usize,
+    memory: usize,
+    pending: Option<Snapshot>,
+    /*The grid as the last step left it, so consecutive updates take one snapshot each.*/
+    last: Option<Snapshot>,
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self::with_memory_limit(DEFAULT_MEMORY_LIMIT)
+    }
+
+    /*The limit is in bytes. The most recent step is always kept.*/
+    pub fn with_memory_limit(memory_limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            memory_limit,
+            memory: 0,
+            pending: None,
+            last: None,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.memory = 0;
+        self.pending = None;
+        self.last = None;
+    }
+
+    /*Starts a step from the grid's current state, replacing any step not yet committed.*/
+    pub fn begin<G: Grid>(&mut self, grid: &G) {
+        self.pending = Some(Snapshot::new(grid));
+    }
+
+    /*Ends the step begun last. Returns whether anything changed; a step that changed
+    something clears the redo steps.*/
+    pub fn commit<G: Grid>(&mut self, grid: &G) -> bool {
+        let before = match self.pending.take() {
+            Some(before) => before,
+            None => return false,
+        };
+        let after = Snapshot::new(grid);
+        let changed = self.record(&before, &after);
+        self.last = Some(after);
+        changed
+    }
+
+    /*Records a single update of the grid as one step.*/
+    pub fn update<G: Grid>(&mut self, grid: &mut G) {
+        self.pending = None;
+        let before = match self.last.take() {
+            Some(last) if last.size == grid.size() && last.generation == grid.generation() => last,
+            _ => Snapshot::new(grid),
+        };
+        grid.update();
+        let after = Snapshot::new(grid);
+        self.record(&before, &after);
+        self.last = Some(after);
+    }
+
+    /*Pushes the step from one snapshot to the other, returning whether it changed
+    anything.*/
+    fn record(&mut self, before: &Snapshot, after: &Snapshot) -> bool {
+        if before.size != after.size {
+            return false;
+        }
+        let words: Vec<u64> = before
+            .words
+            .iter()
+            .zip(&after.words)
+            .map(|(before, after)| before ^ after)
+            .collect();
+        let count = words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum::<usize>();
+        if count == 0 && before.generation == after.generation {
+            return false;
+        }
+
+        let flips = if count * mem::size_of::<u32>() < words.len() * mem::size_of::<u64>() {
+            let mut indices = Vec::with_capacity(count);
+            for (i, &word) in words.iter().enumerate() {
+                let mut word = word;
+                while word != 0 {
+                    indices.push((i * 64 + word.trailing_zeros() as usize) as u32);
+                    word &= word - 1;
+                }
+            }
+            Flips::Sparse(indices)
+        } else {
+            Flips::Dense(words)
+        };
+        self.redo.clear();
+        self.push_undo(Delta {
+            flips,
+            generations: (before.generation, after.generation),
+        });
+        true
+    }
+
+    /*Returns whether there was a step to undo. A step still being recorded is committed
+    first, so it is the one undone.*/
+    pub fn undo<G: Grid>(&mut self, grid: &mut G) -> bool {
+        self.commit(grid);
+        self.last = None;
+        match self.undo.pop_back() {
+            Some(delta) => {
+                self.memory -= delta.memory();
+                delta.apply(grid, delta.generations.0);
+                self.redo.push(delta);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo<G: Grid>(&mut self, grid: &mut G) -> bool {
+        self.pending = None;
+        self.last = None;
+        match self.redo.pop() {
+            Some(delta) => {
+                delta.apply(grid, delta.generations.1);
+                self.push_undo(delta);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn push_undo(&mut self, delta: Delta) {
+        self.memory += delta.memory();
+        self.undo.push_back(delta);
+        while self.memory > self.memory_limit && self.undo.len() > 1 {
+            if let Some(oldest) = self.undo.pop_front() {
+                self.memory -= oldest.memory();
+            }
+        }
+    }
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
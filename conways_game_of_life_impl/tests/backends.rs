@@ -0,0 +1,277 @@
+use conways_game_of_life_impl::{
+    Backend, GenerationStats, Grid, Grid1dVec, Grid2dArr, Grid2dVec, GridPoint, HashLife, Rule,
+    Soup, SparseUniverse, Topology,
+};
+
+const SIZES: [GridPoint; 9] = [
+    (1, 1),
+    (2, 3),
+    (3, 2),
+    (5, 5),
+    (8, 8),
+    (63, 5),
+    (64, 7),
+    (65, 65),
+    (130, 33),
+];
+const TOPOLOGIES: [Topology; 4] = [
+    Topology::Torus,
+    Topology::Plane,
+    Topology::KleinBottle,
+    Topology::CrossSurface,
+];
+const RULES: [&str; 5] = ["B3/S23", "B36/S23", "B2/S", "B3678/S34678", "B0/S8"];
+/*For checks of the update paths rather than the rules.*/
+const FEW_RULES: [&str; 2] = ["B3/S23", "B0/S8"];
+const GENERATIONS: u64 = 8;
+
+fn cells<G: Grid>(grid: &G) -> Vec<bool> {
+    let mut cells = Vec::new();
+    grid.inspect(|point, grid| cells.push(grid.get_cell_unchecked(point)));
+    cells
+}
+
+fn soup<G: Grid>(mut grid: G, topology: Topology, rule: &str, seed: u64) -> G {
+    grid.set_topology(topology);
+    grid.set_rule(rule.parse().unwrap());
+    Soup::new().seed(seed).fill(&mut grid).unwrap();
+    grid
+}
+
+/*Runs a grid alongside a Grid1dVec started from the same soup, checking they agree after
+every generation.*/
+fn check<G: Clone + Grid>(name: &str, grid: G, rules: &[&str], mut update: impl FnMut(&mut G)) {
+    let size = grid.size();
+    for topology in TOPOLOGIES {
+        for &rule in rules {
+            let seed = size.0 as u64 * 1000 + size.1 as u64;
+            let mut expected = soup(Grid1dVec::empty(size), topology, rule, seed);
+            let mut grid = soup(grid.clone(), topology, rule, seed);
+            assert_eq!(cells(&grid), cells(&expected));
+            for generation in 1..=GENERATIONS {
+                expected.update();
+                update(&mut grid);
+                assert_eq!(
+                    cells(&grid),
+                    cells(&expected),
+                    "{} {:?} {:?} {} generation {}",
+                    name,
+                    size,
+                    topology,
+                    rule,
+                    generation
+                );
+                assert_eq!(grid.generation(), generation);
+            }
+        }
+    }
+}
+
+#[test]
+fn backends_match_1d_vec() {
+    for size in SIZES {
+        check("2d_vec", Grid2dVec::empty(size), &RULES, |grid| {
+            grid.update()
+        });
+        for backend in Backend::ALL {
+            check(backend.name(), backend.empty(size), &RULES, |grid| {
+                grid.update()
+            });
+        }
+    }
+}
+
+#[test]
+fn array_grid_matches_1d_vec() {
+    check("2d_arr", Grid2dArr::<5, 5>::empty(), &RULES, |grid| {
+        grid.update()
+    });
+    check("2d_arr", Grid2dArr::<64, 7>::empty(), &RULES, |grid| {
+        grid.update()
+    });
+    check("2d_arr", Grid2dArr::<130, 33>::empty(), &RULES, |grid| {
+        grid.update()
+    });
+}
+
+#[test]
+fn update_parallel_matches_update() {
+    for size in SIZES {
+        /*More threads than some of the grids have rows.*/
+        let threads = 4;
+        check(
+            "1d_vec parallel",
+            Grid1dVec::empty(size),
+            &FEW_RULES,
+            |grid| grid.update_parallel(threads),
+        );
+        /*A storage that does not lend out its next buffer.*/
+        check(
+            "2d_vec parallel",
+            Grid2dVec::empty(size),
+            &FEW_RULES,
+            |grid| grid.update_parallel(threads),
+        );
+        for backend in Backend::ALL {
+            check(backend.name(), backend.empty(size), &FEW_RULES, |grid| {
+                grid.update_parallel(threads)
+            });
+        }
+    }
+}
+
+#[test]
+fn update_with_stats_matches_update() {
+    for size in SIZES {
+        for backend in Backend::ALL {
+            let mut before = Vec::new();
+            check(backend.name(), backend.empty(size), &FEW_RULES, |grid| {
+                before = cells(grid);
+                let stats = grid.update_with_stats();
+                let after = cells(grid);
+                let count = |f: fn(bool, bool) -> bool| {
+                    before
+                        .iter()
+                        .zip(&after)
+                        .filter(|&(&b, &a)| f(b, a))
+                        .count() as u64
+                };
+                assert_eq!(stats.generation(), grid.generation());
+                assert_eq!(stats.population(), count(|_, a| a));
+                assert_eq!(stats.births(), count(|b, a| !b && a));
+                assert_eq!(stats.deaths(), count(|b, a| b && !a));
+                let current = GenerationStats::from_grid(grid);
+                assert_eq!(current.population(), stats.population());
+                assert_eq!(current.bounding_box(), stats.bounding_box());
+
+                let live: Vec<GridPoint> = after
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &a)| a)
+                    .map(|(i, _)| ((i % size.0 as usize) as u16, (i / size.0 as usize) as u16))
+                    .collect();
+                if !live.is_empty() {
+                    let min_max = |f: fn(&GridPoint) -> u16| {
+                        (
+                            live.iter().map(f).min().unwrap(),
+                            live.iter().map(f).max().unwrap(),
+                        )
+                    };
+                    let (x, y) = (min_max(|p| p.0), min_max(|p| p.1));
+                    assert_eq!(stats.bounding_box(), Some(((x.0, y.0), (x.1, y.1))));
+                }
+            });
+        }
+    }
+}
+
+/*The universes are unbounded, so they are checked on a plane big enough that nothing
+reaches the edge.*/
+#[test]
+fn universes_match_1d_vec() {
+    const SIZE: GridPoint = (120, 100);
+    for rule in &RULES[..4] {
+        let rule: Rule = rule.parse().unwrap();
+        let mut expected = Grid1dVec::empty(SIZE);
+        expected.set_topology(Topology::Plane);
+        expected.set_rule(rule);
+        Soup::new()
+            .seed(7)
+            .region((50, 40), (20, 20))
+            .fill(&mut expected)
+            .unwrap();
+
+        let mut sparse = SparseUniverse::from_grid(&expected).unwrap();
+        let mut hashlife = HashLife::from_grid(&expected).unwrap();
+        for generation in 1..=GENERATIONS {
+            expected.update();
+            sparse.update();
+            hashlife.advance(1);
+
+            let mut grid = Grid1dVec::empty(SIZE);
+            sparse.write_to_grid(&mut grid, (0, 0));
+            assert_eq!(
+                cells(&grid),
+                cells(&expected),
+                "sparse {} {}",
+                rule,
+                generation
+            );
+            assert_eq!(sparse.generation(), generation);
+
+            let mut grid = Grid1dVec::empty(SIZE);
+            hashlife.write_to_grid(&mut grid, (0, 0));
+            assert_eq!(
+                cells(&grid),
+                cells(&expected),
+                "hashlife {} {}",
+                rule,
+                generation
+            );
+            assert_eq!(hashlife.generation(), generation);
+        }
+    }
+}
+
+#[test]
+fn universes_reject_b0() {
+    let mut grid = Grid1dVec::empty((4, 4));
+    grid.set_rule("B0/S8".parse().unwrap());
+    assert!(SparseUniverse::from_grid(&grid).is_err());
+    assert!(HashLife::from_grid(&grid).is_err());
+}
+
+/*HashLife jumps by powers of two, so jumps that are not one are checked against as many
+single updates of the sparse universe, both from the start and on top of earlier jumps.*/
+#[test]
+fn hashlife_advance_matches_sparse_updates() {
+    const JUMPS: [u64; 5] = [7, 100, 1000, 1024, 1536];
+    for rule in ["B3/S23", "B36/S23"] {
+        let mut grid = Grid1dVec::empty((64, 64));
+        grid.set_rule(rule.parse().unwrap());
+        Soup::new()
+            .seed(11)
+            .region((22, 22), (20, 20))
+            .fill(&mut grid)
+            .unwrap();
+        let hashlife_cells = |hashlife: &HashLife| {
+            let mut cells = Vec::new();
+            hashlife.for_each_live_cell(|point| cells.push(point));
+            cells.sort_unstable();
+            cells
+        };
+        let sparse_cells = |sparse: &SparseUniverse| {
+            let mut cells: Vec<_> = sparse.live_cells().collect();
+            cells.sort_unstable();
+            cells
+        };
+
+        let mut sparse = SparseUniverse::from_grid(&grid).unwrap();
+        let mut stepped = HashLife::from_grid(&grid).unwrap();
+        for jump in JUMPS {
+            while sparse.generation() < jump {
+                sparse.update();
+            }
+            assert!(sparse.population() > 0);
+            let mut hashlife = HashLife::from_grid(&grid).unwrap();
+            hashlife.advance(jump);
+            assert_eq!(hashlife.generation(), jump);
+            assert_eq!(
+                hashlife_cells(&hashlife),
+                sparse_cells(&sparse),
+                "{} jump {}",
+                rule,
+                jump
+            );
+
+            stepped.advance(jump - stepped.generation());
+            assert_eq!(
+                hashlife_cells(&stepped),
+                sparse_cells(&sparse),
+                "{} jumps up to {}",
+                rule,
+                jump
+            );
+        }
+    }
+}
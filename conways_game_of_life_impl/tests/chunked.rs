@@ -0,0 +1,50 @@
+use conways_game_of_life_impl::{config, Grid, Grid1dVec, GridChunked, Topology};
+
+fn cells<G: Grid>(grid: &G) -> Vec<bool> {
+    let mut cells = Vec::new();
+    grid.inspect(|point, grid| cells.push(grid.get_cell_unchecked(point)));
+    cells
+}
+
+fn place<G: Grid>(grid: &mut G) {
+    grid.set_topology(Topology::Plane);
+    config::glider(grid, (2, 2)).unwrap();
+    config::block(grid, (100, 20)).unwrap();
+}
+
+/*A glider crosses the tiles diagonally on a plane until it runs into the far corner and
+settles, next to a block that never wakes its tiles.*/
+#[test]
+fn tiles_sleep_and_wake_with_a_glider() {
+    const SIZE: (u16, u16) = (160, 160);
+    let mut chunked = GridChunked::with_tile_size(SIZE, 16);
+    let mut expected = Grid1dVec::empty(SIZE);
+    place(&mut chunked);
+    place(&mut expected);
+    assert_eq!(chunked.awake_tile_count(), 100);
+
+    let mut most_awake = 0;
+    for generation in 1..=800 {
+        chunked.update();
+        expected.update();
+        assert_eq!(
+            cells(&chunked),
+            cells(&expected),
+            "generation {}",
+            generation
+        );
+        if generation > 1 {
+            most_awake = most_awake.max(chunked.awake_tile_count());
+        }
+    }
+    /*A glider spans at most four tiles, which with their neighbours is 16.*/
+    assert!(most_awake <= 16, "{} tiles awake", most_awake);
+    assert_ne!(cells(&expected), cells(&Grid1dVec::empty(SIZE)));
+
+    for _ in 0..10 {
+        chunked.update();
+        expected.update();
+    }
+    assert_eq!(cells(&chunked), cells(&expected));
+    assert_eq!(chunked.awake_tile_count(), 0);
+}
@@ -0,0 +1,369 @@
+use conways_game_of_life_impl::{
+    color::Color, write_ppm, ExportError, Exporter, Grid, Grid1dVec, GridPoint, Soup,
+};
+use flate2::{read::ZlibDecoder, Crc};
+use std::io::Read;
+
+const BACKGROUND: Color = Color::rgb(10, 20, 30);
+const CELL: Color = Color::rgb(200, 100, 50);
+const LINE: Color = Color::rgb(1, 2, 3);
+
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+fn soup(size: GridPoint) -> Grid1dVec {
+    let mut grid = Grid1dVec::empty(size);
+    Soup::new().seed(17).fill(&mut grid).unwrap();
+    grid
+}
+
+/*What the exporter should draw, worked out pixel by pixel.*/
+fn expected<G: Grid>(
+    grid: &G,
+    scale: usize,
+    lines: bool,
+    ((x, y), (width, height)): (GridPoint, GridPoint),
+) -> Image {
+    let pitch = scale + lines as usize;
+    let length = |cells: u16| cells as usize * pitch + lines as usize;
+    let (image_width, image_height) = (length(width), length(height));
+    let mut pixels = Vec::with_capacity(image_width * image_height);
+    for pixel_y in 0..image_height {
+        for pixel_x in 0..image_width {
+            pixels.push(if lines && (pixel_x % pitch == 0 || pixel_y % pitch == 0) {
+                LINE
+            } else if grid.get_cell((x + (pixel_x / pitch) as u16, y + (pixel_y / pitch) as u16)) {
+                CELL
+            } else {
+                BACKGROUND
+            });
+        }
+    }
+    Image {
+        width: image_width,
+        height: image_height,
+        pixels,
+    }
+}
+
+fn exporter(scale: u32, lines: bool) -> Exporter {
+    Exporter::new()
+        .scale(scale)
+        .grid_lines(if lines { Some(LINE) } else { None })
+        .background_color(BACKGROUND)
+        .cell_color(CELL)
+}
+
+fn assert_same(image: &Image, expected: &Image) {
+    assert_eq!(
+        (image.width, image.height),
+        (expected.width, expected.height)
+    );
+    if let Some(i) = (0..image.pixels.len()).find(|&i| image.pixels[i] != expected.pixels[i]) {
+        panic!(
+            "pixel ({}, {}) is {:?}, expected {:?}",
+            i % image.width,
+            i / image.width,
+            image.pixels[i],
+            expected.pixels[i]
+        );
+    }
+    assert_eq!(image.pixels.len(), expected.pixels.len());
+}
+
+fn u32_be(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn decode_png(png: &[u8]) -> Image {
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    let mut rest = &png[8..];
+    let (mut width, mut height) = (0, 0);
+    let mut palette = Vec::new();
+    let mut compressed = Vec::new();
+    loop {
+        let length = u32_be(rest) as usize;
+        let (kind, data) = (&rest[4..8], &rest[8..8 + length]);
+        let mut crc = Crc::new();
+        crc.update(kind);
+        crc.update(data);
+        assert_eq!(u32_be(&rest[8 + length..]), crc.sum());
+        rest = &rest[12 + length..];
+        match kind {
+            b"IHDR" => {
+                width = u32_be(data) as usize;
+                height = u32_be(&data[4..]) as usize;
+                assert_eq!(&data[8..], &[8, 3, 0, 0, 0]);
+            }
+            b"PLTE" => {
+                palette = data
+                    .chunks(3)
+                    .map(|rgb| Color::rgb(rgb[0], rgb[1], rgb[2]))
+                    .collect()
+            }
+            b"IDAT" => compressed.extend_from_slice(data),
+            b"IEND" => break,
+            _ => panic!("unexpected chunk {:?}", kind),
+        }
+    }
+    assert!(rest.is_empty());
+
+    let mut raw = Vec::new();
+    ZlibDecoder::new(&compressed[..])
+        .read_to_end(&mut raw)
+        .unwrap();
+    assert_eq!(raw.len(), (width + 1) * height);
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in raw.chunks(width + 1) {
+        assert_eq!(row[0], 0);
+        pixels.extend(row[1..].iter().map(|&index| palette[index as usize]));
+    }
+    Image {
+        width,
+        height,
+        pixels,
+    }
+}
+
+fn decode_ppm(ppm: &[u8]) -> Image {
+    let header: Vec<&[u8]> = ppm.splitn(4, |&byte| byte == b'\n').collect();
+    assert_eq!(header[0], b"P6");
+    assert_eq!(header[2], b"255");
+    let size = String::from_utf8(header[1].to_vec()).unwrap();
+    let (width, height) = size.split_once(' ').unwrap();
+    let (width, height) = (width.parse().unwrap(), height.parse().unwrap());
+    assert_eq!(header[3].len(), width * height * 3);
+    Image {
+        width,
+        height,
+        pixels: header[3]
+            .chunks(3)
+            .map(|rgb| Color::rgb(rgb[0], rgb[1], rgb[2]))
+            .collect(),
+    }
+}
+
+/*A plain GIF LZW decoder, growing the code width once the table fills the current one
+and stopping to add codes at 4096 until the next clear code.*/
+fn decode_lzw(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+    let clear = 1usize << min_code_size;
+    let end = clear + 1;
+    let mut table: Vec<Vec<u8>> = (0..=end).map(|i| vec![i as u8]).collect();
+    let mut code_size = min_code_size as u32 + 1;
+    let mut prev: Option<usize> = None;
+    let mut out = Vec::new();
+    let mut bit = 0;
+    loop {
+        let mut code = 0;
+        for i in 0..code_size as usize {
+            let byte = data[(bit + i) / 8];
+            code |= ((byte >> ((bit + i) % 8)) as usize & 1) << i;
+        }
+        bit += code_size as usize;
+        if code == clear {
+            table.truncate(end + 1);
+            code_size = min_code_size as u32 + 1;
+            prev = None;
+            continue;
+        }
+        if code == end {
+            break;
+        }
+        let entry = match (table.get(code), prev) {
+            (Some(entry), _) => entry.clone(),
+            (None, Some(prev)) if code == table.len() => {
+                let mut entry = table[prev].clone();
+                entry.push(entry[0]);
+                entry
+            }
+            _ => panic!("code {} is not in the table", code),
+        };
+        out.extend_from_slice(&entry);
+        if let Some(prev) = prev {
+            if table.len() < 4096 {
+                let mut added = table[prev].clone();
+                added.push(entry[0]);
+                table.push(added);
+            }
+        }
+        if table.len() == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+        prev = Some(code);
+    }
+    assert_eq!(bit.div_ceil(8), data.len());
+    out
+}
+
+fn decode_gif(gif: &[u8]) -> Vec<Image> {
+    assert_eq!(&gif[..6], b"GIF89a");
+    let u16_le = |bytes: &[u8]| u16::from_le_bytes([bytes[0], bytes[1]]) as usize;
+    let (width, height) = (u16_le(&gif[6..]), u16_le(&gif[8..]));
+    let flags = gif[10];
+    assert_ne!(flags & 0x80, 0);
+    let table_size = 2 << (flags & 7);
+    let palette: Vec<Color> = gif[13..13 + table_size * 3]
+        .chunks(3)
+        .map(|rgb| Color::rgb(rgb[0], rgb[1], rgb[2]))
+        .collect();
+    let mut rest = &gif[13 + table_size * 3..];
+    let mut frames = Vec::new();
+    /*Joins the sub-blocks at the start of rest, leaving rest after the terminator.*/
+    fn sub_blocks(rest: &mut &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        while rest[0] != 0 {
+            let length = rest[0] as usize;
+            data.extend_from_slice(&rest[1..1 + length]);
+            *rest = &rest[1 + length..];
+        }
+        *rest = &rest[1..];
+        data
+    }
+    loop {
+        match rest[0] {
+            0x21 => {
+                rest = &rest[2..];
+                sub_blocks(&mut rest);
+            }
+            0x2c => {
+                assert_eq!(&rest[1..5], &[0, 0, 0, 0]);
+                assert_eq!((u16_le(&rest[5..]), u16_le(&rest[7..])), (width, height));
+                assert_eq!(rest[9], 0);
+                let min_code_size = rest[10];
+                rest = &rest[11..];
+                let indices = decode_lzw(min_code_size, &sub_blocks(&mut rest));
+                assert_eq!(indices.len(), width * height);
+                frames.push(Image {
+                    width,
+                    height,
+                    pixels: indices
+                        .into_iter()
+                        .map(|index| palette[index as usize])
+                        .collect(),
+                });
+            }
+            0x3b => break,
+            byte => panic!("unexpected block {:#x}", byte),
+        }
+    }
+    assert_eq!(rest, &[0x3b]);
+    frames
+}
+
+#[test]
+fn png_round_trip() {
+    let grid = soup((901, 601));
+    let mut png = Vec::new();
+    exporter(1, false).write_png(&grid, &mut png).unwrap();
+    assert_same(
+        &decode_png(&png),
+        &expected(&grid, 1, false, ((0, 0), grid.size())),
+    );
+
+    let region = ((20, 30), (40, 25));
+    let mut png = Vec::new();
+    exporter(3, true)
+        .region(region.0, region.1)
+        .write_png(&grid, &mut png)
+        .unwrap();
+    assert_same(&decode_png(&png), &expected(&grid, 3, true, region));
+}
+
+#[test]
+fn ppm_round_trip() {
+    let grid = soup((90, 60));
+    let region = ((20, 30), (40, 25));
+    let mut ppm = Vec::new();
+    exporter(3, true)
+        .region(region.0, region.1)
+        .write_ppm(&grid, &mut ppm)
+        .unwrap();
+    assert_same(&decode_ppm(&ppm), &expected(&grid, 3, true, region));
+
+    /*Pixels from elsewhere go through the same writer, which checks there are as many
+    as the size says.*/
+    let mut ppm = Vec::new();
+    write_ppm((2, 1), &[1, 2, 3, 4, 5, 6], &mut ppm).unwrap();
+    assert_eq!(ppm, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    let mut ppm = Vec::new();
+    assert!(write_ppm((2, 2), &[1, 2, 3, 4, 5, 6], &mut ppm).is_err());
+    assert!(ppm.is_empty());
+}
+
+/*Big enough that the LZW table fills and is cleared many times in each frame.*/
+#[test]
+fn gif_round_trip() {
+    let mut grid = soup((901, 601));
+    let mut expected_grid = grid.clone();
+    let mut gif = Vec::new();
+    exporter(1, false)
+        .write_gif(&mut grid, 2, 10, &mut gif)
+        .unwrap();
+    let frames = decode_gif(&gif);
+    assert_eq!(frames.len(), 3);
+    for frame in &frames {
+        assert_same(
+            frame,
+            &expected(&expected_grid, 1, false, ((0, 0), expected_grid.size())),
+        );
+        expected_grid.update();
+    }
+    assert_eq!(grid.generation(), 2);
+}
+
+#[test]
+fn gif_round_trip_with_grid_lines() {
+    let mut grid = soup((30, 20));
+    let expected_image = expected(&grid, 2, true, ((5, 5), (10, 8)));
+    let mut gif = Vec::new();
+    exporter(2, true)
+        .region((5, 5), (10, 8))
+        .write_gif(&mut grid, 0, 10, &mut gif)
+        .unwrap();
+    let frames = decode_gif(&gif);
+    assert_eq!(frames.len(), 1);
+    assert_same(&frames[0], &expected_image);
+}
+
+#[test]
+fn empty_and_single_pixel_images() {
+    let mut grid = Grid1dVec::empty((1, 1));
+    let mut gif = Vec::new();
+    exporter(1, false)
+        .write_gif(&mut grid, 0, 10, &mut gif)
+        .unwrap();
+    assert_same(
+        &decode_gif(&gif)[0],
+        &expected(&grid, 1, false, ((0, 0), (1, 1))),
+    );
+
+    let empty = exporter(1, false).region((0, 0), (0, 0));
+    let mut out = Vec::new();
+    assert!(matches!(
+        empty.write_png(&grid, &mut out),
+        Err(ExportError::Empty)
+    ));
+    assert!(matches!(
+        empty.write_gif(&mut grid, 0, 10, &mut out),
+        Err(ExportError::Empty)
+    ));
+    assert!(matches!(
+        empty.write_ppm(&grid, &mut out),
+        Err(ExportError::Empty)
+    ));
+    assert!(out.is_empty());
+
+    /*With grid lines no cells is still the one pixel of line.*/
+    let mut png = Vec::new();
+    exporter(1, true)
+        .region((0, 0), (0, 0))
+        .write_png(&grid, &mut png)
+        .unwrap();
+    assert_same(
+        &decode_png(&png),
+        &expected(&grid, 1, true, ((0, 0), (0, 0))),
+    );
+}
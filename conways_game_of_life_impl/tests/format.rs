@@ -0,0 +1,211 @@
+use conways_game_of_life_impl::{
+    format::{
+        self, life105, life106, plaintext, rle, Format, ParseError, ParseErrorKind, PatternFile,
+    },
+    Rule,
+};
+
+/*A glider with a lone cell far enough away that Life 1.05 splits it into two blocks and
+the RLE lines wrap. The bounding box fills the size, as the coordinate formats need.*/
+fn pattern() -> PatternFile {
+    PatternFile {
+        name: Some(String::from("Glider and friend")),
+        author: Some(String::from("Someone")),
+        comments: vec![String::from("First comment"), String::from("Second")],
+        rule: Some("B36/S23".parse().unwrap()),
+        size: (120, 40),
+        cells: vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2), (119, 39)],
+    }
+}
+
+fn error(result: Result<PatternFile, ParseError>) -> ParseError {
+    result.unwrap_err()
+}
+
+#[test]
+fn rle_round_trip() {
+    let pattern = pattern();
+    let s = rle::write(&pattern);
+    assert!(s.lines().all(|line| line.len() <= 70));
+    assert_eq!(rle::parse(&s).unwrap(), pattern);
+    assert_eq!(Format::detect(&s), Some(Format::Rle));
+}
+
+#[test]
+fn plaintext_round_trip() {
+    let pattern = PatternFile {
+        rule: None,
+        ..pattern()
+    };
+    let s = plaintext::write(&pattern);
+    assert_eq!(plaintext::parse(&s).unwrap(), pattern);
+    assert_eq!(Format::detect(&s), Some(Format::Plaintext));
+}
+
+#[test]
+fn life105_round_trip() {
+    let pattern = pattern();
+    let s = life105::write(&pattern);
+    assert_eq!(s.matches("#P").count(), 2);
+    assert_eq!(life105::parse(&s).unwrap(), pattern);
+    assert_eq!(Format::detect(&s), Some(Format::Life105));
+
+    let conway = PatternFile {
+        rule: Some(Rule::CONWAY),
+        ..pattern
+    };
+    assert_eq!(life105::parse(&life105::write(&conway)).unwrap(), conway);
+}
+
+#[test]
+fn life106_round_trip() {
+    let pattern = PatternFile {
+        rule: None,
+        ..pattern()
+    };
+    let s = life106::write(&pattern);
+    assert_eq!(life106::parse(&s).unwrap(), pattern);
+    assert_eq!(Format::detect(&s), Some(Format::Life106));
+}
+
+#[test]
+fn detect() {
+    assert_eq!(
+        Format::detect("#N Name\nx = 3, y = 1\n3o!"),
+        Some(Format::Rle)
+    );
+    assert_eq!(Format::detect("\n  x = 1, y = 1\no!"), Some(Format::Rle));
+    assert_eq!(Format::detect("!Name: x\n.O.\n"), Some(Format::Plaintext));
+    assert_eq!(Format::detect(".O.\n..O\nOOO\n"), Some(Format::Plaintext));
+    assert_eq!(
+        Format::detect("#Life 1.05\n#P 0 0\n*\n"),
+        Some(Format::Life105)
+    );
+    assert_eq!(Format::detect("\n#Life 1.06\n0 0\n"), Some(Format::Life106));
+    assert_eq!(Format::detect(""), None);
+    assert_eq!(Format::detect("#C comment only\n"), None);
+    assert_eq!(Format::detect("hello\n"), None);
+    assert!(matches!(
+        error(format::parse("hello")).kind(),
+        ParseErrorKind::UnknownFormat
+    ));
+}
+
+#[test]
+fn coordinates_far_apart_are_too_large() {
+    assert!(matches!(
+        error(format::parse(
+            "#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n"
+        ))
+        .kind(),
+        ParseErrorKind::PatternTooLarge
+    ));
+    assert!(matches!(
+        error(format::parse("#Life 1.06\n0 0\n70000 0\n")).kind(),
+        ParseErrorKind::PatternTooLarge
+    ));
+    assert!(matches!(
+        error(format::parse("#Life 1.05\n#P 9223372036854775807 0\n.*\n")).kind(),
+        ParseErrorKind::PatternTooLarge
+    ));
+    assert!(matches!(
+        error(format::parse(
+            "#Life 1.05\n#P -9223372036854775808 0\n*\n#P 9223372036854775807 0\n*\n"
+        ))
+        .kind(),
+        ParseErrorKind::PatternTooLarge
+    ));
+    assert!(matches!(
+        error(format::parse("x = 70000, y = 1\no!")).kind(),
+        ParseErrorKind::PatternTooLarge
+    ));
+}
+
+#[test]
+fn malformed_rle() {
+    assert!(matches!(
+        error(rle::parse("")).kind(),
+        ParseErrorKind::MissingHeader
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3\n3o!")).kind(),
+        ParseErrorKind::InvalidHeader
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1, z = 2\n3o!")).kind(),
+        ParseErrorKind::InvalidHeader
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1\n4o!")).kind(),
+        ParseErrorKind::CellOutsidePattern
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1\nb18446744073709551615o!")).kind(),
+        ParseErrorKind::CellOutsidePattern
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1\n$18446744073709551615$o!")).kind(),
+        ParseErrorKind::CellOutsidePattern
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1\n99999999999999999999o!")).kind(),
+        ParseErrorKind::InvalidRunCount
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1\n3o")).kind(),
+        ParseErrorKind::MissingTerminator
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1\n3q!")).kind(),
+        ParseErrorKind::UnexpectedCharacter('q')
+    ));
+    assert!(matches!(
+        error(rle::parse("x = 3, y = 1, rule = B9/S\n3o!")).kind(),
+        ParseErrorKind::InvalidRule(_)
+    ));
+
+    let e = rle::parse("x = 3, y = 2\no$\n2bx!").unwrap_err();
+    assert_eq!((e.line(), e.column()), (3, 3));
+}
+
+#[test]
+fn malformed_plaintext() {
+    let e = plaintext::parse("!Name: x\n.O.\n.Ox\n").unwrap_err();
+    assert!(matches!(e.kind(), ParseErrorKind::UnexpectedCharacter('x')));
+    assert_eq!((e.line(), e.column()), (3, 3));
+    assert!(matches!(
+        error(plaintext::parse(&".".repeat(70000))).kind(),
+        ParseErrorKind::PatternTooLarge
+    ));
+}
+
+#[test]
+fn malformed_life105() {
+    assert!(matches!(
+        error(life105::parse("#P 0 0\n*\n")).kind(),
+        ParseErrorKind::MissingHeader
+    ));
+    assert!(matches!(
+        error(life105::parse("#Life 1.05\n#P 0\n*\n")).kind(),
+        ParseErrorKind::InvalidCoordinate
+    ));
+    assert!(matches!(
+        error(life105::parse("#Life 1.05\n#P 0 0\n*x\n")).kind(),
+        ParseErrorKind::UnexpectedCharacter('x')
+    ));
+}
+
+#[test]
+fn malformed_life106() {
+    assert!(matches!(
+        error(life106::parse("0 0\n")).kind(),
+        ParseErrorKind::MissingHeader
+    ));
+    let e = life106::parse("#Life 1.06\n0 0\n1 y\n").unwrap_err();
+    assert!(matches!(e.kind(), ParseErrorKind::InvalidCoordinate));
+    assert_eq!((e.line(), e.column()), (3, 3));
+    assert!(matches!(
+        error(life106::parse("#Life 1.06\n0 0 0\n")).kind(),
+        ParseErrorKind::InvalidCoordinate
+    ));
+}
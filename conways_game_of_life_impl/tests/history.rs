@@ -0,0 +1,81 @@
+use conways_game_of_life_impl::{config, Grid, Grid1dVec, History, Repeat, Topology};
+
+/*Records the grid and each of the given number of generations after it, returning the
+first repeat found and the generation it ended on.*/
+fn run<G: Grid>(grid: &mut G, history: &mut History, generations: u64) -> Option<Repeat> {
+    history.record(grid);
+    for _ in 0..generations {
+        grid.update();
+        if let Some(repeat) = history.record(grid) {
+            assert_eq!(repeat.generation(), history.generation() - 1);
+            assert_eq!(Some(repeat), history.first_repeat());
+            return Some(repeat);
+        }
+    }
+    assert_eq!(history.first_repeat(), None);
+    None
+}
+
+fn grid(topology: Topology) -> Grid1dVec {
+    let mut grid = Grid1dVec::empty((12, 10));
+    grid.set_topology(topology);
+    grid
+}
+
+#[test]
+fn still_life_has_period_one() {
+    let mut grid = grid(Topology::Torus);
+    config::block(&mut grid, (3, 3)).unwrap();
+    let repeat = run(&mut grid, &mut History::new(), 10).unwrap();
+    assert_eq!((repeat.start(), repeat.period()), (0, 1));
+    assert!(!repeat.is_translated());
+}
+
+#[test]
+fn blinker_has_period_two() {
+    for topology in [Topology::Torus, Topology::Plane] {
+        let mut grid = grid(topology);
+        config::blinker(&mut grid, (4, 4)).unwrap();
+        let repeat = run(&mut grid, &mut History::new(), 10).unwrap();
+        assert_eq!((repeat.start(), repeat.period()), (0, 2), "{:?}", topology);
+        assert_eq!(repeat.offset(), (0, 0));
+    }
+}
+
+#[test]
+fn glider_repeats_moved_on_a_torus() {
+    let mut grid = grid(Topology::Torus);
+    config::glider(&mut grid, (4, 4)).unwrap();
+    let repeat = run(&mut grid, &mut History::new(), 10).unwrap();
+    assert_eq!((repeat.start(), repeat.period()), (0, 4));
+    assert_eq!(repeat.offset(), (1, 1));
+    assert!(repeat.is_translated());
+
+    /*Past the right and bottom edges the offset still says which way it went.*/
+    let mut history = History::new();
+    grid.step(20);
+    assert_eq!(run(&mut grid, &mut history, 10).unwrap().offset(), (1, 1));
+
+    /*Cleared, the history starts counting again.*/
+    history.clear();
+    assert_eq!((history.generation(), history.first_repeat()), (0, None));
+    let repeat = run(&mut grid, &mut history, 10).unwrap();
+    assert_eq!((repeat.start(), repeat.generation()), (0, 4));
+}
+
+#[test]
+fn no_repeat_within_the_window() {
+    /*A glider on a plane is never a shifted copy of an earlier state.*/
+    let mut grid = grid(Topology::Plane);
+    config::glider(&mut grid, (0, 0)).unwrap();
+    assert_eq!(run(&mut grid, &mut History::new(), 20), None);
+
+    /*On a torus it repeats every 4 generations, too long for 3 states.*/
+    let mut grid = self::grid(Topology::Torus);
+    config::glider(&mut grid, (0, 0)).unwrap();
+    assert_eq!(run(&mut grid, &mut History::with_capacity(3), 40), None);
+    assert_eq!(
+        run(&mut grid, &mut History::with_capacity(4), 40).map(|repeat| repeat.period()),
+        Some(4)
+    );
+}
@@ -0,0 +1,82 @@
+use conways_game_of_life_impl::{Rule, RuleParseError};
+
+fn rule(s: &str) -> Rule {
+    s.parse().unwrap()
+}
+
+#[test]
+fn notations_agree() {
+    let high_life = Rule::new(&[3, 6], &[2, 3]).unwrap();
+    for s in [
+        "B36/S23",
+        "b36/s23",
+        "S23/B36",
+        "s23/B36",
+        "23/36",
+        " B63/S32 ",
+    ] {
+        assert_eq!(rule(s), high_life, "{}", s);
+    }
+    assert_eq!(rule("B3/S23"), Rule::CONWAY);
+    assert_eq!(rule("B/S"), Rule::new(&[], &[]).unwrap());
+    assert_eq!(rule("/"), Rule::new(&[], &[]).unwrap());
+}
+
+#[test]
+fn display_round_trips() {
+    for s in [
+        "B3/S23",
+        "B36/S23",
+        "B3678/S34678",
+        "B2/S",
+        "B/S012345678",
+        "B0/S8",
+    ] {
+        assert_eq!(rule(s).to_string(), s);
+        assert_eq!(rule(&rule(s).to_string()), rule(s));
+    }
+    /*Other notations come back in canonical form.*/
+    assert_eq!(rule("s23/b63").to_string(), "B36/S23");
+    assert_eq!(rule("23/3").to_string(), "B3/S23");
+    assert_eq!(format!("{:?}", Rule::CONWAY), "Rule(B3/S23)");
+}
+
+#[test]
+fn rejected_rulestrings() {
+    let error = |s: &str| s.parse::<Rule>().unwrap_err();
+    assert!(matches!(error("B3S23"), RuleParseError::MissingSeparator));
+    assert!(matches!(error(""), RuleParseError::MissingSeparator));
+    assert!(matches!(
+        error("B39/S23"),
+        RuleParseError::InvalidCharacter('9')
+    ));
+    assert!(matches!(
+        error("B3/S2x"),
+        RuleParseError::InvalidCharacter('x')
+    ));
+    assert!(matches!(
+        error("B3/S23/"),
+        RuleParseError::InvalidCharacter('/')
+    ));
+    assert!(matches!(
+        error("B33/S23"),
+        RuleParseError::RepeatedNeighbourCount(3)
+    ));
+    assert!(matches!(
+        error("B3/S232"),
+        RuleParseError::RepeatedNeighbourCount(2)
+    ));
+    assert!(matches!(error("B3/B23"), RuleParseError::MixedNotation));
+    assert!(matches!(error("S3/s23"), RuleParseError::MixedNotation));
+    assert!(matches!(error("B3/23"), RuleParseError::MixedNotation));
+    assert!(matches!(error("23/S3"), RuleParseError::MixedNotation));
+
+    assert!(matches!(
+        Rule::new(&[9], &[]),
+        Err(RuleParseError::InvalidNeighbourCount(9))
+    ));
+    assert!(matches!(
+        Rule::new(&[3], &[2, 3, 2]),
+        Err(RuleParseError::RepeatedNeighbourCount(2))
+    ));
+}
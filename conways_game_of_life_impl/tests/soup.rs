@@ -0,0 +1,203 @@
+use conways_game_of_life_impl::{Density, Grid, Grid1dVec, GridPoint, Soup, SoupError, Symmetry};
+
+const SYMMETRIES: [Symmetry; 6] = [
+    Symmetry::C1,
+    Symmetry::C2,
+    Symmetry::C4,
+    Symmetry::D2,
+    Symmetry::D4,
+    Symmetry::D8,
+];
+
+fn live_cells<G: Grid>(grid: &G) -> Vec<GridPoint> {
+    let mut cells = Vec::new();
+    grid.inspect(|point, grid| {
+        if grid.get_cell_unchecked(point) {
+            cells.push(point);
+        }
+    });
+    cells
+}
+
+fn population<G: Grid>(grid: &G) -> usize {
+    live_cells(grid).len()
+}
+
+/*The maps each symmetry must leave the soup unchanged under, in the coordinates of a
+width by height rectangle.*/
+fn maps(
+    symmetry: Symmetry,
+    (width, height): GridPoint,
+) -> Vec<Box<dyn Fn(GridPoint) -> GridPoint>> {
+    let rotate_90 = move |(x, y): GridPoint| (height - 1 - y, x);
+    let rotate_180 = move |(x, y): GridPoint| (width - 1 - x, height - 1 - y);
+    let mirror_x = move |(x, y): GridPoint| (width - 1 - x, y);
+    let mirror_y = move |(x, y): GridPoint| (x, height - 1 - y);
+    let diagonal = |(x, y): GridPoint| (y, x);
+    match symmetry {
+        Symmetry::C1 => vec![],
+        Symmetry::C2 => vec![Box::new(rotate_180)],
+        Symmetry::C4 => vec![Box::new(rotate_90)],
+        Symmetry::D2 => vec![Box::new(mirror_x)],
+        Symmetry::D4 => vec![Box::new(mirror_x), Box::new(mirror_y)],
+        Symmetry::D8 => vec![Box::new(rotate_90), Box::new(mirror_x), Box::new(diagonal)],
+    }
+}
+
+#[test]
+fn soups_have_their_symmetry() {
+    for symmetry in SYMMETRIES {
+        let sizes: &[GridPoint] = match symmetry {
+            Symmetry::C4 | Symmetry::D8 => &[(11, 11), (12, 12)],
+            _ => &[(11, 11), (12, 9), (9, 12)],
+        };
+        for &size in sizes {
+            for seed in 0..4 {
+                /*The soup fills a region, leaving the cells around it alone.*/
+                let mut grid = Grid1dVec::empty((20, 20));
+                grid.inspect_mut(|point, grid| grid.set_cell_unchecked(point, true));
+                let top_left = (3, 2);
+                Soup::new()
+                    .seed(seed)
+                    .symmetry(symmetry)
+                    .region(top_left, size)
+                    .fill(&mut grid)
+                    .unwrap();
+
+                let cell = |(x, y): GridPoint| grid.get_cell((top_left.0 + x, top_left.1 + y));
+                let mut live = 0;
+                for y in 0..size.1 {
+                    for x in 0..size.0 {
+                        live += cell((x, y)) as usize;
+                        for map in maps(symmetry, size) {
+                            assert_eq!(
+                                cell(map((x, y))),
+                                cell((x, y)),
+                                "{:?} {:?} seed {} at {:?}",
+                                symmetry,
+                                size,
+                                seed,
+                                (x, y)
+                            );
+                        }
+                    }
+                }
+                assert!(live > 0 && live < size.0 as usize * size.1 as usize);
+                assert_eq!(
+                    population(&grid) - live,
+                    20 * 20 - size.0 as usize * size.1 as usize
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn square_symmetries_need_a_square() {
+    for symmetry in [Symmetry::C4, Symmetry::D8] {
+        let mut grid = Grid1dVec::empty((12, 9));
+        assert!(matches!(
+            Soup::new().symmetry(symmetry).fill(&mut grid),
+            Err(SoupError::NotSquare(s, (12, 9))) if s == symmetry
+        ));
+        assert_eq!(population(&grid), 0);
+    }
+}
+
+#[test]
+fn seeds_reproduce_soups() {
+    let mut grid = Grid1dVec::empty((30, 30));
+    let seed = Soup::new().fill(&mut grid).unwrap();
+    let mut again = Grid1dVec::empty((30, 30));
+    Soup::new().seed(seed).fill(&mut again).unwrap();
+    assert_eq!(live_cells(&grid), live_cells(&again));
+}
+
+#[test]
+fn uniform_density() {
+    let fill = |density| {
+        let mut grid = Grid1dVec::empty((200, 200));
+        Soup::new()
+            .seed(1)
+            .density(Density::Uniform(density))
+            .fill(&mut grid)
+            .unwrap();
+        population(&grid) as f64 / 40_000.
+    };
+    assert_eq!(fill(0.), 0.);
+    assert_eq!(fill(1.), 1.);
+    for density in [0.1, 0.25, 0.5, 0.9] {
+        let measured = fill(density);
+        assert!(
+            (measured - density).abs() < 0.02,
+            "{} {}",
+            density,
+            measured
+        );
+    }
+}
+
+/*One full cluster lies within its radius of its centre, so its cells fit in a square
+twice the radius across, and are densest near the middle.*/
+#[test]
+fn clustered_density() {
+    for seed in 0..8 {
+        let mut grid = Grid1dVec::empty((100, 100));
+        Soup::new()
+            .seed(seed)
+            .density(Density::Clustered {
+                count: 1,
+                radius: 10.,
+                density: 1.,
+            })
+            .fill(&mut grid)
+            .unwrap();
+        let cells = live_cells(&grid);
+        assert!(!cells.is_empty());
+        let span = |coordinate: fn(&GridPoint) -> u16| {
+            let (min, max) = (
+                cells.iter().map(coordinate).min().unwrap(),
+                cells.iter().map(coordinate).max().unwrap(),
+            );
+            max - min
+        };
+        assert!(span(|point| point.0) < 20, "seed {}", seed);
+        assert!(span(|point| point.1) < 20, "seed {}", seed);
+    }
+
+    let mut grid = Grid1dVec::empty((100, 100));
+    Soup::new()
+        .density(Density::Clustered {
+            count: 5,
+            radius: 10.,
+            density: 0.,
+        })
+        .fill(&mut grid)
+        .unwrap();
+    assert_eq!(population(&grid), 0);
+}
+
+/*With the range narrowed to one value, noise is uniform at that value.*/
+#[test]
+fn noise_density() {
+    for (min, max, expected) in [(0., 0., 0.), (1., 1., 1.), (0.3, 0.3, 0.3), (0.2, 0.6, 0.4)] {
+        let mut grid = Grid1dVec::empty((200, 200));
+        Soup::new()
+            .seed(2)
+            .density(Density::Noise {
+                scale: 8.,
+                min,
+                max,
+            })
+            .fill(&mut grid)
+            .unwrap();
+        let measured = population(&grid) as f64 / 40_000.;
+        assert!(
+            (measured - expected).abs() < 0.05,
+            "{} {} {}",
+            min,
+            max,
+            measured
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use conways_game_of_life_impl::SparseUniverse;
+
+fn live_cells(universe: &SparseUniverse) -> Vec<(i64, i64)> {
+    let mut cells: Vec<_> = universe.live_cells().collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn set_line_joins_far_apart_points() {
+    let mut universe = SparseUniverse::empty();
+    universe.set_line((i64::MIN, i64::MIN), (i64::MIN + 4, i64::MIN + 2), true);
+    assert_eq!(
+        live_cells(&universe),
+        vec![
+            (i64::MIN, i64::MIN),
+            (i64::MIN + 1, i64::MIN + 1),
+            (i64::MIN + 2, i64::MIN + 1),
+            (i64::MIN + 3, i64::MIN + 2),
+            (i64::MIN + 4, i64::MIN + 2),
+        ]
+    );
+
+    let mut universe = SparseUniverse::empty();
+    universe.set_line((i64::MAX, i64::MAX - 3), (i64::MAX - 1, i64::MAX), true);
+    assert_eq!(
+        live_cells(&universe),
+        vec![
+            (i64::MAX - 1, i64::MAX - 1),
+            (i64::MAX - 1, i64::MAX),
+            (i64::MAX, i64::MAX - 3),
+            (i64::MAX, i64::MAX - 2),
+        ]
+    );
+}
+
+#[test]
+fn set_line_matches_the_exact_line() {
+    let mut universe = SparseUniverse::empty();
+    universe.set_line((0, 0), (6, -3), true);
+    assert_eq!(
+        live_cells(&universe),
+        vec![(0, 0), (1, -1), (2, -1), (3, -2), (4, -2), (5, -3), (6, -3)]
+    );
+}
+
+#[test]
+fn set_hline_wraps_at_the_edge() {
+    let mut universe = SparseUniverse::empty();
+    universe.set_hline(i64::MAX - 1, 4, 0, true);
+    assert_eq!(
+        live_cells(&universe),
+        vec![
+            (i64::MIN, 0),
+            (i64::MIN + 1, 0),
+            (i64::MAX - 1, 0),
+            (i64::MAX, 0)
+        ]
+    );
+
+    universe.set_vline(i64::MAX, 2, 7, true);
+    assert!(universe.get_cell((7, i64::MAX)));
+    assert!(universe.get_cell((7, i64::MIN)));
+    assert_eq!(universe.population(), 6);
+}
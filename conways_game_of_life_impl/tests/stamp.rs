@@ -0,0 +1,121 @@
+use conways_game_of_life_impl::{
+    config, Grid, Grid1dVec, GridPoint, GridUnit, Pattern, StampMode, Topology,
+};
+
+fn live_cells<G: Grid>(grid: &G) -> Vec<GridPoint> {
+    let mut cells = Vec::new();
+    grid.inspect(|point, grid| {
+        if grid.get_cell_unchecked(point) {
+            cells.push((point.1, point.0));
+        }
+    });
+    cells.sort_unstable();
+    cells.into_iter().map(|(y, x)| (x, y)).collect()
+}
+
+fn grid(topology: Topology) -> Grid1dVec {
+    let mut grid = Grid1dVec::empty((5, 4));
+    grid.set_topology(topology);
+    grid
+}
+
+/*An L of three cells, so mirroring shows.*/
+fn pattern() -> Pattern {
+    Pattern::new((2, 2), vec![(0, 0), (0, 1), (1, 1)]).unwrap()
+}
+
+#[test]
+fn stamp_wraps_on_a_torus() {
+    let mut grid = grid(Topology::Torus);
+    grid.stamp(&pattern(), (4, 3), StampMode::Or).unwrap();
+    assert_eq!(live_cells(&grid), vec![(0, 0), (4, 0), (4, 3)]);
+}
+
+#[test]
+fn stamp_clips_on_a_plane() {
+    let mut grid = grid(Topology::Plane);
+    grid.stamp(&pattern(), (4, 3), StampMode::Or).unwrap();
+    assert_eq!(live_cells(&grid), vec![(4, 3)]);
+}
+
+#[test]
+fn stamp_mirrors_on_a_klein_bottle() {
+    let mut grid = grid(Topology::KleinBottle);
+    grid.stamp(&pattern(), (3, 3), StampMode::Or).unwrap();
+    assert_eq!(live_cells(&grid), vec![(0, 0), (1, 0), (3, 3)]);
+
+    let mut grid = self::grid(Topology::CrossSurface);
+    grid.stamp(&pattern(), (4, 2), StampMode::Or).unwrap();
+    assert_eq!(live_cells(&grid), vec![(0, 0), (4, 2), (4, 3)]);
+}
+
+#[test]
+fn stamp_overwrites_across_edges() {
+    let mut grid = grid(Topology::Torus);
+    grid.inspect_mut(|point, grid| grid.set_cell_unchecked(point, true));
+    grid.stamp(&pattern(), (4, 3), StampMode::Overwrite)
+        .unwrap();
+    assert!(!grid.get_cell((0, 3)));
+    assert!(grid.get_cell((4, 0)));
+    assert!(grid.get_cell((3, 3)));
+    assert!(grid.get_cell((0, 0)));
+
+    grid.stamp(&pattern(), (4, 3), StampMode::Xor).unwrap();
+    assert!(!grid.get_cell((4, 0)));
+    assert!(!grid.get_cell((4, 3)));
+}
+
+#[test]
+fn stamp_rejects_what_cannot_fit() {
+    let mut grid = grid(Topology::Torus);
+    assert!(grid.stamp(&pattern(), (5, 0), StampMode::Or).is_err());
+    let wide = Pattern::new((6, 1), vec![(0, 0), (5, 0)]).unwrap();
+    assert!(grid.stamp(&wide, (0, 0), StampMode::Or).is_err());
+    assert!(live_cells(&grid).is_empty());
+
+    let mut grid = self::grid(Topology::Plane);
+    grid.stamp(&wide, (0, 0), StampMode::Or).unwrap();
+    assert_eq!(live_cells(&grid), vec![(0, 0)]);
+}
+
+#[test]
+fn config_places_near_edges() {
+    let mut grid = Grid1dVec::empty((10, 10));
+    config::glider(&mut grid, (8, 8)).unwrap();
+    assert_eq!(live_cells(&grid).len(), 5);
+}
+
+#[test]
+fn transformations_round_trip() {
+    let rotated = pattern().rotate_90();
+    assert_eq!(rotated.cells(), &[(0, 0), (1, 0), (0, 1)]);
+    for pattern in [
+        pattern(),
+        Pattern::glider(),
+        Pattern::lwss(),
+        Pattern::blinker(),
+    ] {
+        let turned = |times| (0..times).fold(pattern.clone(), |pattern, _| pattern.rotate_90());
+        assert_eq!(turned(4), pattern);
+        assert_eq!(turned(2), pattern.rotate_180());
+        assert_eq!(turned(3), pattern.rotate_270());
+        assert_eq!(pattern.rotate_90().rotate_270(), pattern);
+        assert_eq!(pattern.flip_horizontal().flip_horizontal(), pattern);
+        assert_eq!(pattern.flip_vertical().flip_vertical(), pattern);
+        assert_eq!(
+            pattern.flip_horizontal().flip_vertical(),
+            pattern.rotate_180()
+        );
+    }
+}
+
+#[test]
+fn translate_grows_the_pattern() {
+    let moved = pattern().translate((2, 1)).unwrap();
+    assert_eq!(moved.size(), (4, 3));
+    assert_eq!(moved.cells(), &[(2, 1), (2, 2), (3, 2)]);
+    assert_eq!(pattern().translate((0, 0)), Some(pattern()));
+    assert_eq!(pattern().translate((GridUnit::MAX - 1, 0)), None);
+    assert_eq!(pattern().translate((0, GridUnit::MAX)), None);
+    assert!(pattern().translate((GridUnit::MAX - 2, 0)).is_some());
+}
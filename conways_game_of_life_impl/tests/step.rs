@@ -0,0 +1,136 @@
+use conways_game_of_life_impl::{
+    config, Grid, Grid1dVec, GridPoint, StopCondition, StopReason, Topology,
+};
+
+fn grid(cells: &[GridPoint]) -> Grid1dVec {
+    let mut grid = Grid1dVec::empty((12, 10));
+    for &point in cells {
+        grid.set_cell(point, true).unwrap();
+    }
+    grid
+}
+
+/*A diagonal of three cells: the ends die at once and the middle one after.*/
+const DIAGONAL: [GridPoint; 3] = [(4, 4), (5, 5), (6, 6)];
+
+#[test]
+fn stops_when_extinct() {
+    let mut grid = grid(&[]);
+    grid.step(4);
+    for &point in &DIAGONAL {
+        grid.set_cell(point, true).unwrap();
+    }
+    let stop = grid.step_until(10, &[StopCondition::Extinct]);
+    assert_eq!(stop.reason(), StopReason::Extinct);
+    assert_eq!((stop.steps(), stop.generation()), (2, 6));
+    assert_eq!(grid.generation(), 6);
+}
+
+#[test]
+fn stops_when_periodic() {
+    let mut grid = grid(&[]);
+    config::blinker(&mut grid, (4, 4)).unwrap();
+    grid.step(3);
+    let stop = grid.step_until(10, &[StopCondition::Periodic]);
+    let StopReason::Periodic(repeat) = stop.reason() else {
+        panic!("{:?}", stop.reason());
+    };
+    /*The repeat counts generations from the start of the step.*/
+    assert_eq!(
+        (repeat.start(), repeat.period(), repeat.offset()),
+        (0, 2, (0, 0))
+    );
+    assert_eq!((stop.steps(), stop.generation()), (2, 5));
+
+    let mut grid = self::grid(&[]);
+    config::glider(&mut grid, (4, 4)).unwrap();
+    let stop = grid.step_until(10, &[StopCondition::Periodic]);
+    let StopReason::Periodic(repeat) = stop.reason() else {
+        panic!("{:?}", stop.reason());
+    };
+    assert_eq!((repeat.period(), repeat.offset()), (4, (1, 1)));
+    assert_eq!((stop.steps(), stop.generation()), (4, 4));
+}
+
+#[test]
+fn stops_at_the_generation_limit() {
+    let mut grid = grid(&[]);
+    config::blinker(&mut grid, (4, 4)).unwrap();
+    let conditions = [
+        StopCondition::Extinct,
+        StopCondition::PopulationCrosses(4),
+        StopCondition::CellChanged((0, 0)),
+    ];
+    let stop = grid.step_until(10, &conditions);
+    assert_eq!(stop.reason(), StopReason::Completed);
+    assert_eq!((stop.steps(), stop.generation()), (10, 10));
+
+    let stop = grid.step_until(0, &conditions);
+    assert_eq!(stop.reason(), StopReason::Completed);
+    assert_eq!((stop.steps(), stop.generation()), (0, 10));
+
+    let stop = grid.step(7);
+    assert_eq!(stop.reason(), StopReason::Completed);
+    assert_eq!((stop.steps(), stop.generation()), (7, 17));
+}
+
+/*A population equal to the threshold counts as above it.*/
+#[test]
+fn stops_when_population_crosses() {
+    let mut grid = grid(&DIAGONAL);
+    let stop = grid.step_until(10, &[StopCondition::PopulationCrosses(2)]);
+    assert_eq!(stop.reason(), StopReason::PopulationCrosses(2, 1));
+    assert_eq!(stop.generation(), 1);
+
+    /*Three cells of a block grow into the whole block.*/
+    let mut grid = self::grid(&[(4, 4), (5, 4), (4, 5)]);
+    let stop = grid.step_until(10, &[StopCondition::PopulationCrosses(4)]);
+    assert_eq!(stop.reason(), StopReason::PopulationCrosses(4, 4));
+    assert_eq!(stop.generation(), 1);
+    let stop = grid.step_until(10, &[StopCondition::PopulationCrosses(4)]);
+    assert_eq!(stop.reason(), StopReason::Completed);
+}
+
+#[test]
+fn stops_when_cells_change() {
+    let mut grid = grid(&[]);
+    config::glider(&mut grid, (0, 0)).unwrap();
+    let stop = grid.step_until(40, &[StopCondition::CellChanged((5, 5))]);
+    assert_eq!(stop.reason(), StopReason::CellChanged((5, 5)));
+    assert!(grid.get_cell((5, 5)));
+    let cell_generation = stop.generation();
+    assert!(cell_generation > 4);
+
+    let mut grid = self::grid(&[]);
+    config::glider(&mut grid, (0, 0)).unwrap();
+    let region = StopCondition::RegionChanged((5, 5), (20, 20));
+    let stop = grid.step_until(40, &[region]);
+    assert_eq!(stop.reason(), StopReason::RegionChanged((5, 5), (20, 20)));
+    assert!(stop.generation() <= cell_generation);
+    /*The parts of the region outside the grid never change.*/
+    let stop = grid.step_until(40, &[StopCondition::RegionChanged((12, 0), (5, 5))]);
+    assert_eq!(stop.reason(), StopReason::Completed);
+}
+
+/*When several conditions hold at once, the first one listed is given.*/
+#[test]
+fn first_condition_listed_wins() {
+    let conditions = [StopCondition::PopulationCrosses(1), StopCondition::Extinct];
+    let mut grid = grid(&[(4, 4), (6, 6)]);
+    assert_eq!(
+        grid.step_until(10, &conditions).reason(),
+        StopReason::PopulationCrosses(1, 0)
+    );
+    let mut grid = self::grid(&[(4, 4), (6, 6)]);
+    assert_eq!(
+        grid.step_until(10, &[conditions[1], conditions[0]])
+            .reason(),
+        StopReason::Extinct
+    );
+
+    let mut grid = self::grid(&[]);
+    grid.set_topology(Topology::Plane);
+    let stop = grid.step_until(10, &[StopCondition::Periodic, StopCondition::Extinct]);
+    assert!(matches!(stop.reason(), StopReason::Periodic(_)));
+    assert_eq!(stop.generation(), 1);
+}
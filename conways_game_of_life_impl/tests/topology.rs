@@ -0,0 +1,87 @@
+use conways_game_of_life_impl::{config, Backend, Grid, GridPoint, Topology};
+
+const SIZE: GridPoint = (5, 4);
+const TOPOLOGIES: [Topology; 4] = [
+    Topology::Torus,
+    Topology::Plane,
+    Topology::KleinBottle,
+    Topology::CrossSurface,
+];
+
+fn live_cells<G: Grid>(grid: &G) -> Vec<GridPoint> {
+    let mut cells = Vec::new();
+    grid.inspect(|point, grid| {
+        if grid.get_cell_unchecked(point) {
+            cells.push(point);
+        }
+    });
+    cells
+}
+
+#[test]
+fn points_inside_map_to_themselves() {
+    for topology in TOPOLOGIES {
+        for point in [(0, 0), (4, 0), (0, 3), (4, 3), (2, 1)] {
+            assert_eq!(
+                topology.map_point((point.0 as i32, point.1 as i32), SIZE),
+                Some(point),
+                "{:?}",
+                topology
+            );
+        }
+    }
+}
+
+/*Points just past each edge and corner of a 5x4 grid.*/
+#[test]
+fn points_outside_map_across_the_edges() {
+    let cases = [
+        /*Torus, plane, Klein bottle, cross-surface.*/
+        ((-1, 1), [Some((4, 1)), None, Some((4, 1)), Some((4, 2))]),
+        ((5, 0), [Some((0, 0)), None, Some((0, 0)), Some((0, 3))]),
+        ((1, -1), [Some((1, 3)), None, Some((3, 3)), Some((3, 3))]),
+        ((0, 4), [Some((0, 0)), None, Some((4, 0)), Some((4, 0))]),
+        ((-1, -1), [Some((4, 3)), None, Some((0, 3)), Some((0, 0))]),
+        ((5, -1), [Some((0, 3)), None, Some((4, 3)), Some((4, 0))]),
+        ((-1, 4), [Some((4, 0)), None, Some((0, 0)), Some((0, 3))]),
+        ((5, 4), [Some((0, 0)), None, Some((4, 0)), Some((4, 3))]),
+    ];
+    for (point, expected) in cases {
+        for (topology, expected) in TOPOLOGIES.iter().zip(expected) {
+            assert_eq!(
+                topology.map_point(point, SIZE),
+                expected,
+                "{:?} {:?}",
+                topology,
+                point
+            );
+        }
+    }
+}
+
+/*A glider heading down and to the right on an 8x8 grid. On a torus it comes back to
+where it started every 32 generations, while on a plane it runs into the corner and
+settles into a block.*/
+#[test]
+fn glider_wraps_on_a_torus_but_not_on_a_plane() {
+    for backend in Backend::ALL {
+        let mut grid = backend.empty((8, 8));
+        config::glider(&mut grid, (2, 2)).unwrap();
+        let start = live_cells(&grid);
+        for _ in 0..2 {
+            grid.step(32);
+            assert_eq!(live_cells(&grid), start, "{}", backend);
+        }
+
+        let mut grid = backend.empty((8, 8));
+        grid.set_topology(Topology::Plane);
+        config::glider(&mut grid, (2, 2)).unwrap();
+        grid.step(64);
+        assert_eq!(
+            live_cells(&grid),
+            vec![(6, 6), (7, 6), (6, 7), (7, 7)],
+            "{}",
+            backend
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use conways_game_of_life_impl::{Grid, Grid1dVec, Soup, UndoHistory};
+
+fn cells<G: Grid>(grid: &G) -> Vec<bool> {
+    let mut cells = Vec::new();
+    grid.inspect(|point, grid| cells.push(grid.get_cell_unchecked(point)));
+    cells
+}
+
+#[test]
+fn undo_retraces_updates_and_edits() {
+    let mut grid = Grid1dVec::empty((40, 30));
+    Soup::new().seed(3).fill(&mut grid).unwrap();
+    let mut history = UndoHistory::new();
+    let mut states = vec![cells(&grid)];
+
+    for step in 0..6 {
+        if step == 3 {
+            history.begin(&grid);
+            let b = grid.get_cell((5, 5));
+            grid.set_cell((5, 5), !b).unwrap();
+            assert!(history.commit(&grid));
+        } else {
+            history.update(&mut grid);
+        }
+        states.push(cells(&grid));
+    }
+
+    /*Undoing partway and updating again must start from the undone state.*/
+    history.undo(&mut grid);
+    history.undo(&mut grid);
+    assert_eq!(cells(&grid), states[4]);
+    history.update(&mut grid);
+    let mut expected = Grid1dVec::empty((40, 30));
+    Soup::new().seed(3).fill(&mut expected).unwrap();
+    for _ in 0..3 {
+        expected.update();
+    }
+    let b = expected.get_cell((5, 5));
+    expected.set_cell((5, 5), !b).unwrap();
+    expected.update();
+    assert_eq!(cells(&grid), cells(&expected));
+    states.truncate(5);
+    states.push(cells(&grid));
+
+    for state in states.iter().rev().skip(1) {
+        assert!(history.undo(&mut grid));
+        assert_eq!(&cells(&grid), state);
+    }
+    assert!(!history.undo(&mut grid));
+    while history.redo(&mut grid) {}
+    assert_eq!(cells(&grid), cells(&expected));
+}
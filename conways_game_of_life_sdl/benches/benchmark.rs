@@ -1,7 +1,7 @@
 use conways_game_of_life_sdl::{
     config,
-    render::{CyclicalModulatorOpt, DrawOption, Rygcbm},
-    Grid, Grid1dVec, Grid2dArr, Grid2dVec, GridPoint, GridUnit, SDLInterfaceBuilder,
+    render::{CyclicalModulatorOpt, DrawOption, RenderMode, Rygcbm},
+    Backend, Grid, Grid1dVec, Grid2dArr, Grid2dVec, GridPoint, GridUnit, SDLInterfaceBuilder,
 };
 use criterion::{
     criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
@@ -38,21 +38,30 @@ fn init_benchmark<G: Clone + Grid>(
     mut grid: G,
     group: &mut BenchmarkGroup<'_, WallTime>,
 ) {
-    grid.set_fps(0);
     config::random(&mut grid, 0.25).unwrap();
     let size = grid.size();
 
-    add_interface(name, grid.clone(), get_builder(size), group);
+    for (suffix, render_mode) in [("", RenderMode::Rects), ("_texture", RenderMode::Texture)] {
+        let mut interface_builder = get_builder(size);
+        interface_builder.renderer_builder.render_mode = render_mode;
+        add_interface(
+            &format!("{}{}", name, suffix),
+            grid.clone(),
+            interface_builder,
+            group,
+        );
 
-    let mut interface_builder = get_builder(size);
-    interface_builder.renderer_builder.draw_opt =
-        DrawOption::DynamicCyclical(CyclicalModulatorOpt::Rygcbm(Rygcbm::Red));
-    add_interface(
-        &format!("{}_cyclical_rygcbm", name),
-        grid,
-        interface_builder,
-        group,
-    );
+        let mut interface_builder = get_builder(size);
+        interface_builder.renderer_builder.render_mode = render_mode;
+        interface_builder.renderer_builder.draw_opt =
+            DrawOption::DynamicCyclical(CyclicalModulatorOpt::Rygcbm(Rygcbm::Red));
+        add_interface(
+            &format!("{}_cyclical_rygcbm{}", name, suffix),
+            grid.clone(),
+            interface_builder,
+            group,
+        );
+    }
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -71,6 +80,9 @@ fn criterion_benchmark(c: &mut Criterion) {
         Grid2dArr::<WIDTH, HEIGHT>::empty(),
         &mut group,
     );
+    for backend in Backend::ALL {
+        init_benchmark(&format!("{}_dyn", backend), backend.empty(size), &mut group);
+    }
 
     group.finish();
 }
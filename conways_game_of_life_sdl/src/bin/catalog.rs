@@ -0,0 +1,45 @@
+use conways_game_of_life_sdl::{
+    catalog,
+    render::{CyclicalModulatorOpt, DrawOption, Rygcbm},
+    Grid, Grid2dVec, GridUnit, SDLInterfaceBuilder, StampMode,
+};
+use std::{env, error::Error, io};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    const WIDTH: GridUnit = 600;
+    const HEIGHT: GridUnit = 600;
+    let entry = match env::args().nth(1).as_deref().map(catalog::find) {
+        Some(Some(entry)) => entry,
+        Some(None) | None => {
+            println!("Usage: catalog <pattern name>");
+            return Ok(catalog::write_list(io::stdout())?);
+        }
+    };
+
+    let pattern = entry.pattern();
+    let (width, height) = pattern.size();
+    let mut grid = Grid2dVec::empty((WIDTH, HEIGHT));
+    grid.stamp(
+        &pattern,
+        (
+            WIDTH.saturating_sub(width) / 2,
+            HEIGHT.saturating_sub(height) / 2,
+        ),
+        StampMode::Or,
+    )?;
+
+    let mut interface_builder = SDLInterfaceBuilder::new()?;
+    interface_builder
+        .renderer_builder
+        .video_subsystem_command(move |mut vss| {
+            vss.window_size = (WIDTH as u32, HEIGHT as u32);
+            vss
+        })
+        .canvas_builder_command(|cb| cb.present_vsync());
+    interface_builder.renderer_builder.draw_opt =
+        DrawOption::DynamicCyclical(CyclicalModulatorOpt::Rygcbm(Rygcbm::Red));
+
+    let mut interface = interface_builder.build(grid)?;
+    interface.run()?;
+    Ok(())
+}
@@ -1,19 +1,21 @@
 use conways_game_of_life_sdl::{
     render::{CyclicalModulatorOpt, DrawOption, Rygcbm},
-    Grid2dArr, SDLInterfaceBuilder,
+    Backend, GridUnit, SDLInterfaceBuilder,
 };
-use std::error::Error;
+use std::{env, error::Error};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    const WIDTH: usize = 600;
-    const HEIGHT: usize = 600;
-    let grid = Grid2dArr::<WIDTH, HEIGHT>::empty();
+    let mut args = env::args().skip(1);
+    let backend: Backend = args.next().map_or(Ok(Backend::default()), |s| s.parse())?;
+    let width: GridUnit = args.next().map_or(Ok(600), |s| s.parse())?;
+    let height: GridUnit = args.next().map_or(Ok(width), |s| s.parse())?;
+    let grid = backend.empty((width, height));
 
     let mut interface_builder = SDLInterfaceBuilder::new()?;
     interface_builder
         .renderer_builder
         .video_subsystem_command(move |mut vss| {
-            vss.window_size = (WIDTH as u32, HEIGHT as u32);
+            vss.window_size = (width as u32, height as u32);
             vss
         })
         .canvas_builder_command(|cb| cb.present_vsync());
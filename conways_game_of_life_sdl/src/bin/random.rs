@@ -1,21 +1,29 @@
 use conways_game_of_life_sdl::{
-    config,
     render::{CyclicalModulatorOpt, DrawOption, Rygcbm},
-    Grid2dArr, SDLInterfaceBuilder,
+    Backend, Density, GridUnit, SDLInterfaceBuilder, Soup,
 };
-use std::error::Error;
+use std::{env, error::Error};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    const WIDTH: usize = 600;
-    const HEIGHT: usize = 600;
-    let mut grid = Grid2dArr::<WIDTH, HEIGHT>::empty();
-    config::random(&mut grid, 0.25)?;
+    /*The seed comes first so that replaying a run only needs the seed it printed; a dash
+    picks a random one while still choosing the other arguments.*/
+    let mut args = env::args().skip(1);
+    let seed = args.next().filter(|s| s != "-");
+    let backend: Backend = args.next().map_or(Ok(Backend::default()), |s| s.parse())?;
+    let width: GridUnit = args.next().map_or(Ok(600), |s| s.parse())?;
+    let height: GridUnit = args.next().map_or(Ok(width), |s| s.parse())?;
+    let mut grid = backend.empty((width, height));
+    let mut soup = Soup::new().density(Density::Uniform(0.25));
+    if let Some(seed) = seed {
+        soup = soup.seed(seed.parse()?);
+    }
+    println!("Seed: {}", soup.fill(&mut grid)?);
 
     let mut interface_builder = SDLInterfaceBuilder::new()?;
     interface_builder
         .renderer_builder
         .video_subsystem_command(move |mut vss| {
-            vss.window_size = (WIDTH as u32, HEIGHT as u32);
+            vss.window_size = (width as u32, height as u32);
             vss
         })
         .canvas_builder_command(|cb| cb.present_vsync());
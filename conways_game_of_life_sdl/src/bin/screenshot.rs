@@ -0,0 +1,58 @@
+use conways_game_of_life_sdl::{
+    catalog, render::RendererBuilder, write_ppm, Grid, Grid2dVec, GridUnit, StampMode,
+};
+use std::{
+    env,
+    error::Error,
+    fs::File,
+    io::{self, BufWriter, Write},
+    process,
+};
+
+/*Renders a catalog pattern after some generations without opening a window, and writes
+the picture as a binary PPM. Exits with 2 if the arguments are invalid, like life.*/
+fn main() -> Result<(), Box<dyn Error>> {
+    const WIDTH: GridUnit = 200;
+    const HEIGHT: GridUnit = 150;
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (entry, generations, path) = match args.as_slice() {
+        [name, generations, path] => match (catalog::find(name), generations.parse::<u64>()) {
+            (Some(entry), Ok(generations)) => (entry, generations, path),
+            _ => usage(),
+        },
+        _ => usage(),
+    };
+
+    let pattern = entry.pattern();
+    let (width, height) = pattern.size();
+    let mut grid = Grid2dVec::empty((WIDTH, HEIGHT));
+    grid.stamp(
+        &pattern,
+        (
+            WIDTH.saturating_sub(width) / 2,
+            HEIGHT.saturating_sub(height) / 2,
+        ),
+        StampMode::Or,
+    )?;
+    grid.step(generations);
+
+    let mut renderer = RendererBuilder::offscreen().build_offscreen(grid.size(), (800, 600))?;
+    renderer.camera.zoom(3);
+    renderer.render_with_cursor(&grid, None)?;
+    let (width, height) = renderer.size()?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_ppm(
+        (width as usize, height as usize),
+        &renderer.read_pixels()?,
+        &mut writer,
+    )?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: screenshot <pattern name> <generations> <output.ppm>");
+    /*The list is only a hint, so failing to print it changes nothing.*/
+    let _ = catalog::write_list(io::stderr());
+    process::exit(2);
+}
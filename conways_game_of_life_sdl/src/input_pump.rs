@@ -1,7 +1,7 @@
 use crate::{BResult, Grid, GridPoint, IResult};
 use sdl2::{
     event::Event,
-    keyboard::Keycode,
+    keyboard::{Keycode, Mod},
     mouse::{MouseButton, MouseState, MouseUtil},
     EventPump, Sdl,
 };
@@ -43,8 +43,10 @@ pub enum Input {
     OneFrame,
     Pause,
     Quit,
+    Redo,
     Reset,
     Run,
+    Undo,
     ZoomCamera { zoom: i32 },
 }
 
@@ -66,6 +68,15 @@ impl InputPump {
                 keycode: Some(Keycode::Escape),
                 ..
             } => Input::Quit,
+            Event::KeyDown {
+                keycode: Some(key),
+                keymod,
+                ..
+            } if keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) => match key {
+                Keycode::Y => Input::Redo,
+                Keycode::Z => Input::Undo,
+                _ => Input::Run,
+            },
             Event::KeyDown {
                 keycode: Some(key), ..
             } => match key {
@@ -2,11 +2,13 @@ mod input_pump;
 pub mod render;
 
 pub use conways_game_of_life_impl::{
-    config, BResult, Grid, Grid1dVec, Grid2dArr, Grid2dVec, GridPoint, GridUnit,
+    catalog, color, config, write_ppm, BResult, Backend, Density, DynGrid, ExportError, Exporter,
+    Grid, Grid1dVec, Grid2dArr, Grid2dVec, GridBitPacked, GridChunked, GridPoint, GridUnit,
+    Pattern, Rule, Soup, StampMode, Symmetry, Topology, UndoHistory,
 };
 use input_pump::{Input, InputPump};
 use render::{Renderer, RendererBuilder};
-use sdl2::{video::WindowBuildError, IntegerOrSdlError, Sdl};
+use sdl2::{render::TextureValueError, video::WindowBuildError, IntegerOrSdlError, Sdl};
 use std::{error::Error, fmt, marker::PhantomData};
 
 pub type IResult<T> = Result<T, InterfaceError>;
@@ -15,6 +17,7 @@ pub type IResult<T> = Result<T, InterfaceError>;
 pub enum InterfaceError {
     IntegerOrSdlError(IntegerOrSdlError),
     String(String),
+    TextureValueError(TextureValueError),
     WindowBuildError(WindowBuildError),
 }
 
@@ -32,6 +35,12 @@ impl From<String> for InterfaceError {
     }
 }
 
+impl From<TextureValueError> for InterfaceError {
+    fn from(tve: TextureValueError) -> Self {
+        Self::TextureValueError(tve)
+    }
+}
+
 impl From<WindowBuildError> for InterfaceError {
     fn from(wbe: WindowBuildError) -> Self {
         Self::WindowBuildError(wbe)
@@ -76,6 +85,7 @@ where
             _sdl: self.sdl,
             init_grid: grid.clone(),
             grid,
+            history: UndoHistory::new(),
             pause: false,
         })
     }
@@ -90,10 +100,11 @@ where
     input_pump: InputPump,
     init_grid: G,
     grid: G,
+    history: UndoHistory,
     pause: bool,
 }
 
-impl<'a, G> SDLInterface<G>
+impl<G> SDLInterface<G>
 where
     G: Clone + Grid,
 {
@@ -112,6 +123,9 @@ where
                         .renderer
                         .map_window_pos_to_cell(point, self.grid.size())
                     {
+                        if !self.history.is_recording() {
+                            self.history.begin(&self.grid);
+                        }
                         if let Err(oob) = self.input_pump.draw(&mut self.grid, point) {
                             println!(
                                 "Warning: could not toggle point: {:?} in grid {:?}",
@@ -133,17 +147,33 @@ where
                 Input::OneFrame => one_frame = true,
                 Input::Pause => self.pause = !self.pause,
                 Input::Quit => run = false,
+                Input::Redo => {
+                    self.pause = true;
+                    self.history.redo(&mut self.grid);
+                }
                 Input::Run => (),
                 Input::Reset => {
                     self.renderer.reset();
+                    self.history.begin(&self.grid);
                     self.grid = self.init_grid.clone();
+                    self.history.commit(&self.grid);
+                }
+                Input::Undo => {
+                    self.pause = true;
+                    self.history.undo(&mut self.grid);
                 }
                 Input::ZoomCamera { zoom } => self.renderer.camera.zoom(zoom.signum()),
             }
         }
+        let update = !self.pause || one_frame;
+        /*A stroke is one step of the history, unless the grid updates while drawing, which
+        splits it around the update.*/
+        if self.history.is_recording() && (self.input_pump.draw_state.is_none() || update) {
+            self.history.commit(&self.grid);
+        }
         self.renderer.render(&self.grid, &self.input_pump)?;
-        if !self.pause || one_frame {
-            self.grid.update();
+        if update {
+            self.history.update(&mut self.grid);
             self.renderer.update();
         }
         Ok(run)
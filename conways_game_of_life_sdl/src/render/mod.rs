@@ -1,12 +1,14 @@
-mod new_cell_color;
-
-use crate::{input_pump::InputPump, Grid, GridPoint, GridUnit, IResult};
-use new_cell_color::{CyclicalModulator, NewCellColorCyclical, NewCellColorHeatMap};
-pub use new_cell_color::{CyclicalModulatorOpt, Rgb, Rygcbm};
+pub use crate::color::{CyclicalModulatorOpt, Rgb, Rygcbm};
+use crate::{
+    color::{self, CyclicalModulator, NewCellColorCyclical, NewCellColorHeatMap},
+    input_pump::InputPump,
+    Exporter, Grid, GridPoint, GridUnit, IResult,
+};
 use sdl2::{
-    pixels::Color,
+    pixels::{Color, PixelFormatEnum},
     rect::Rect,
-    render::{CanvasBuilder, WindowCanvas},
+    render::{Canvas, CanvasBuilder, RenderTarget, Texture, WindowCanvas},
+    surface::Surface,
     video::{Window, WindowBuilder},
     Sdl, VideoSubsystem,
 };
@@ -24,6 +26,37 @@ enum DrawOptionPrivate {
     DynamicHeatMap(NewCellColorHeatMap),
 }
 
+impl DrawOptionPrivate {
+    fn new(draw_opt: DrawOption, grid_size: GridPoint) -> Self {
+        match draw_opt {
+            DrawOption::Static(color) => Self::Static(color),
+            DrawOption::DynamicCyclical(rgb) => Self::DynamicCyclical(NewCellColorCyclical::new(
+                CyclicalModulator::new(rgb),
+                grid_size,
+            )),
+            DrawOption::DynamicHeatMap { hot, cold } => {
+                Self::DynamicHeatMap(NewCellColorHeatMap::new(hot, cold, grid_size))
+            }
+        }
+    }
+
+    fn get_cell_color(&mut self, point: GridPoint, cell: bool) -> Color {
+        match self {
+            Self::Static(color) => *color,
+            Self::DynamicCyclical(ncc) => sdl_color(ncc.get_cell_color(point, cell)),
+            Self::DynamicHeatMap(ncc) => sdl_color(ncc.get_cell_color(point, cell)),
+        }
+    }
+}
+
+fn sdl_color(color: color::Color) -> Color {
+    Color::RGB(color.r, color.g, color.b)
+}
+
+fn impl_color(color: Color) -> color::Color {
+    color::Color::rgb(color.r, color.g, color.b)
+}
+
 type Zoom = i32;
 
 #[derive(Clone, Copy)]
@@ -59,6 +92,61 @@ impl Camera {
         self.zoom += zoom;
         self.zoom = self.zoom.clamp(self.zoom_range.0, self.zoom_range.1);
     }
+
+    /*The cells at least partly inside a window of the given size, as the first cell and
+    the cell past the last in each direction. One cell of slack is kept on each side to
+    allow for rounding.*/
+    fn visible_cells(
+        &self,
+        window_size: (u32, u32),
+        grid_size: GridPoint,
+    ) -> (GridPoint, GridPoint) {
+        let zoom = self.zoom as f64;
+        let range = |camera: f64, window: u32, grid: GridUnit| {
+            let half = (window / 2) as f64 / zoom;
+            let start = (camera - half).floor() - 1.;
+            let end = (camera - half + window as f64 / zoom).ceil() + 1.;
+            let clamp = |v: f64| v.clamp(0., grid as f64) as GridUnit;
+            (clamp(start), clamp(end))
+        };
+        let x = range(self.x, window_size.0, grid_size.0);
+        let y = range(self.y, window_size.1, grid_size.1);
+        ((x.0, y.0), (x.1, y.1))
+    }
+}
+
+/*Rects fills a rectangle per live cell. Texture writes a texel per cell into a streaming
+texture the size of the grid and draws it with one copy, falling back to rects for grids
+larger than the renderer's largest texture.*/
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RenderMode {
+    Rects,
+    Texture,
+}
+
+/*Must be given the canvas that the renderer keeps, as the texture is destroyed along
+with it when the renderer is dropped. A maximum size of 0 means the renderer has no
+limit.*/
+fn create_texture<T: RenderTarget>(
+    render_mode: RenderMode,
+    canvas: &Canvas<T>,
+    grid_size: GridPoint,
+) -> IResult<Option<Texture>> {
+    let info = canvas.info();
+    let fits = |length: GridUnit, max: u32| max == 0 || length as u32 <= max;
+    Ok(match render_mode {
+        RenderMode::Texture
+            if fits(grid_size.0, info.max_texture_width)
+                && fits(grid_size.1, info.max_texture_height) =>
+        {
+            Some(canvas.create_texture_streaming(
+                PixelFormatEnum::RGB888,
+                grid_size.0 as u32,
+                grid_size.1 as u32,
+            )?)
+        }
+        RenderMode::Texture | RenderMode::Rects => None,
+    })
 }
 
 pub enum CameraOpt {
@@ -66,6 +154,22 @@ pub enum CameraOpt {
     Position { x: f64, y: f64 },
 }
 
+impl CameraOpt {
+    fn camera(&self, grid_size: GridPoint) -> Camera {
+        let zoom = 1;
+        let zoom_range = (1, 20);
+        match *self {
+            Self::Centered => Camera::new(
+                (grid_size.0 / 2) as f64,
+                (grid_size.1 / 2) as f64,
+                zoom,
+                zoom_range,
+            ),
+            Self::Position { x, y } => Camera::new(x, y, zoom, zoom_range),
+        }
+    }
+}
+
 enum RendererBuildStage {
     VideoSubsystem(VideoSubsystemStage),
     WindowBuilder(WindowBuilder),
@@ -102,13 +206,16 @@ impl StageCommands {
     }
 }
 
+/*new takes the video subsystem from SDL. Offscreen builders have none and can only
+build_offscreen.*/
 pub struct RendererBuilder {
-    pub video: VideoSubsystem,
+    video: Option<VideoSubsystem>,
     pub grid_background_color: Color,
     pub background_color: Color,
     pub cursor_color: Color,
     pub draw_opt: DrawOption,
     pub camera_opt: CameraOpt,
+    pub render_mode: RenderMode,
     build_stage: RendererBuildStage,
     stage_commands: StageCommands,
 }
@@ -142,27 +249,13 @@ macro_rules! process_stages {
 	    )+
 	        RendererBuildStage::Canvas(mut canvas) => {
 		    apply_command!($self, canvas, canvas);
-		    let zoom = 1;
-		    let zoom_range = (1, 20);
                     return Ok(Renderer {
-			camera: match $self.camera_opt {
-			    CameraOpt::Centered => {
-				Camera::new(($grid_size.0/2) as f64, ($grid_size.1/2) as f64, zoom, zoom_range)
-			    }
-			    CameraOpt::Position {x, y} => Camera::new(x, y, zoom, zoom_range),
-			},
+			camera: $self.camera_opt.camera($grid_size),
 			grid_background_color: $self.grid_background_color,
 			background_color: $self.background_color,
 			cursor_color: $self.cursor_color,
-			draw_opt: match $self.draw_opt {
-			    DrawOption::Static(color) => DrawOptionPrivate::Static(color),
-			    DrawOption::DynamicCyclical(rgb) => DrawOptionPrivate::DynamicCyclical(
-				NewCellColorCyclical::new(CyclicalModulator::new(rgb), $grid_size),
-			    ),
-			    DrawOption::DynamicHeatMap { hot, cold } => {
-				DrawOptionPrivate::DynamicHeatMap(NewCellColorHeatMap::new(hot, cold, $grid_size))
-			    }
-			},
+			draw_opt: DrawOptionPrivate::new($self.draw_opt, $grid_size),
+			texture_opt: create_texture($self.render_mode, &canvas, $grid_size)?,
                         _video: $self.video,
                         canvas,
                     });
@@ -175,18 +268,40 @@ macro_rules! process_stages {
 impl RendererBuilder {
     pub fn new(sdl: &Sdl) -> IResult<Self> {
         Ok(Self {
+            video: Some(sdl.video()?),
+            ..Self::offscreen()
+        })
+    }
+
+    /*For rendering without a display, as the video subsystem is not initialised.*/
+    pub fn offscreen() -> Self {
+        Self {
             draw_opt: DrawOption::Static(Color::RGB(200, 200, 200)),
             grid_background_color: Color::RGB(0, 0, 0),
             background_color: Color::RGB(25, 25, 25),
             cursor_color: Color::RGB(255, 255, 255),
-            video: sdl.video()?,
+            video: None,
             camera_opt: CameraOpt::Centered,
+            render_mode: RenderMode::Rects,
             build_stage: RendererBuildStage::VideoSubsystem(VideoSubsystemStage {
                 window_name: "conways_game_of_life".into(),
                 window_size: (800, 600),
             }),
             stage_commands: StageCommands::new(),
-        })
+        }
+    }
+
+    /*An exporter drawing in this renderer's colours. Dynamic colours are exported as the
+    colour new cells start with.*/
+    pub fn exporter(&self) -> Exporter {
+        let cell_color = match self.draw_opt {
+            DrawOption::Static(color) => impl_color(color),
+            DrawOption::DynamicCyclical(opt) => CyclicalModulator::new(opt).color(),
+            DrawOption::DynamicHeatMap { hot, .. } => hot.into(),
+        };
+        Exporter::new()
+            .background_color(impl_color(self.grid_background_color))
+            .cell_color(cell_color)
     }
 
     set_command!(
@@ -209,6 +324,8 @@ impl RendererBuilder {
                 WindowBuilder,
                 vss,
                 self.video
+                    .as_ref()
+                    .ok_or_else(|| String::from("no video subsystem"))?
                     .window(&vss.window_name, vss.window_size.0, vss.window_size.1,)
             ],
             [WindowBuilder, window_builder, Window, wb, wb.build()?],
@@ -216,19 +333,50 @@ impl RendererBuilder {
             [CanvasBuilder, canvas_builder, Canvas, cb, cb.build()?],
         );
     }
+
+    /*Renders into a software surface of the given size in pixels rather than a window.
+    The window and canvas commands are not applied.*/
+    pub fn build_offscreen(
+        self,
+        grid_size: GridPoint,
+        (width, height): (u32, u32),
+    ) -> IResult<Renderer<Surface<'static>>> {
+        let canvas = Surface::new(width, height, PixelFormatEnum::RGB888)?.into_canvas()?;
+        Ok(Renderer {
+            camera: self.camera_opt.camera(grid_size),
+            grid_background_color: self.grid_background_color,
+            background_color: self.background_color,
+            cursor_color: self.cursor_color,
+            draw_opt: DrawOptionPrivate::new(self.draw_opt, grid_size),
+            texture_opt: create_texture(self.render_mode, &canvas, grid_size)?,
+            _video: self.video,
+            canvas,
+        })
+    }
 }
 
-pub struct Renderer {
+pub struct Renderer<T: RenderTarget = Window> {
     pub camera: Camera,
     pub grid_background_color: Color,
     pub background_color: Color,
     pub cursor_color: Color,
-    _video: VideoSubsystem,
-    canvas: WindowCanvas,
+    _video: Option<VideoSubsystem>,
+    canvas: Canvas<T>,
     draw_opt: DrawOptionPrivate,
+    texture_opt: Option<Texture>,
 }
 
-impl Renderer {
+impl<T: RenderTarget> Renderer<T> {
+    /*Width and height in pixels.*/
+    pub fn size(&self) -> IResult<(u32, u32)> {
+        Ok(self.canvas.output_size()?)
+    }
+
+    /*The pixels last rendered, as RGB rows from the top.*/
+    pub fn read_pixels(&self) -> IResult<Vec<u8>> {
+        Ok(self.canvas.read_pixels(None, PixelFormatEnum::RGB24)?)
+    }
+
     pub fn map_window_pos_to_cell(
         &self,
         (x, y): (i32, i32),
@@ -251,7 +399,7 @@ impl Renderer {
             None
         }
 
-        let window_size = self.canvas.window().size();
+        let window_size = self.canvas.output_size().ok()?;
         let window_h_w = window_size.0 as i32 / 2;
         let window_h_h = window_size.1 as i32 / 2;
         let zoom_f64 = self.camera.zoom as f64;
@@ -263,13 +411,27 @@ impl Renderer {
     }
 
     pub fn render<G: Grid>(&mut self, grid: &G, input_pump: &InputPump) -> IResult<()> {
+        let cursor_opt = if input_pump.mouse_in_window() {
+            Some(input_pump.mouse().position())
+        } else {
+            None
+        };
+        self.render_with_cursor(grid, cursor_opt)
+    }
+
+    /*Renders with the cursor over the cell under the given window position, if any.*/
+    pub fn render_with_cursor<G: Grid>(
+        &mut self,
+        grid: &G,
+        cursor_opt: Option<(i32, i32)>,
+    ) -> IResult<()> {
         self.canvas.set_draw_color(self.background_color);
         self.canvas.clear();
 
+        let window_size = self.canvas.output_size()?;
         let get_rect = {
             let camera = (self.camera.x, self.camera.y);
 
-            let window_size = self.canvas.window().size();
             let window_h_w = window_size.0 as i32 / 2;
             let window_h_h = window_size.1 as i32 / 2;
 
@@ -290,34 +452,64 @@ impl Renderer {
         self.canvas.set_draw_color(self.grid_background_color);
         self.canvas.fill_rect(get_rect((0, 0), grid_size))?;
 
-        if let DrawOptionPrivate::Static(cell_color) = self.draw_opt {
-            self.canvas.set_draw_color(cell_color);
-        }
-
-        grid.try_inspect::<String, _>(|point, grid| {
-            let cell = grid.get_cell_unchecked(point);
-            match &mut self.draw_opt {
-                DrawOptionPrivate::DynamicCyclical(ncc) => {
-                    self.canvas.set_draw_color(ncc.get_cell_color(point, cell));
+        let (start, end) = self.camera.visible_cells(window_size, grid_size);
+        let visible = |(x, y): GridPoint| start.0 <= x && x < end.0 && start.1 <= y && y < end.1;
+        /*With nothing in view the rects path still keeps dynamic colours right.*/
+        if let (Some(texture), true) = (&mut self.texture_opt, start.0 < end.0 && start.1 < end.1) {
+            let draw_opt = &mut self.draw_opt;
+            let grid_background_color = self.grid_background_color;
+            let size = (end.0 - start.0, end.1 - start.1);
+            let rect = Rect::new(start.0 as i32, start.1 as i32, size.0 as u32, size.1 as u32);
+            texture.with_lock(rect, |texels, pitch| {
+                /*As with rects, dynamic colours need every cell visited.*/
+                let (rows, columns) = if let DrawOptionPrivate::Static(_) = draw_opt {
+                    (start.1..end.1, start.0..end.0)
+                } else {
+                    (0..grid_size.1, 0..grid_size.0)
+                };
+                for y in rows {
+                    for x in columns.clone() {
+                        let cell = grid.get_cell_unchecked((x, y));
+                        let color = draw_opt.get_cell_color((x, y), cell);
+                        if visible((x, y)) {
+                            let color = if cell { color } else { grid_background_color };
+                            let texel = (y - start.1) as usize * pitch + (x - start.0) as usize * 4;
+                            texels[texel..texel + 4].copy_from_slice(
+                                &u32::from_be_bytes([0, color.r, color.g, color.b]).to_ne_bytes(),
+                            );
+                        }
+                    }
                 }
-                DrawOptionPrivate::DynamicHeatMap(ncc) => {
-                    self.canvas.set_draw_color(ncc.get_cell_color(point, cell));
+            })?;
+            self.canvas.copy(texture, rect, get_rect(start, size))?;
+        } else if let DrawOptionPrivate::Static(cell_color) = self.draw_opt {
+            self.canvas.set_draw_color(cell_color);
+            for y in start.1..end.1 {
+                for x in start.0..end.0 {
+                    if grid.get_cell_unchecked((x, y)) {
+                        self.canvas.fill_rect(get_rect((x, y), (1, 1)))?;
+                    }
                 }
-                _ => (),
-            }
-            if cell {
-                self.canvas.fill_rect(get_rect(point, (1, 1)))?;
-            }
-            Ok(())
-        })?;
-
-        if input_pump.mouse_in_window() {
-            if let Some(point) =
-                self.map_window_pos_to_cell(input_pump.mouse().position(), grid_size)
-            {
-                self.canvas.set_draw_color(self.cursor_color);
-                self.canvas.draw_rect(get_rect(point, (1, 1)))?;
             }
+        } else {
+            /*Dynamic colours are taken when a cell is born, so cells out of view are
+            still visited to keep their colours right, but not drawn.*/
+            grid.try_inspect::<String, _>(|point, grid| {
+                let cell = grid.get_cell_unchecked(point);
+                let color = self.draw_opt.get_cell_color(point, cell);
+                if cell && visible(point) {
+                    self.canvas.set_draw_color(color);
+                    self.canvas.fill_rect(get_rect(point, (1, 1)))?;
+                }
+                Ok(())
+            })?;
+        }
+
+        if let Some(point) =
+            cursor_opt.and_then(|cursor| self.map_window_pos_to_cell(cursor, grid_size))
+        {
+            self.canvas.set_draw_color(self.cursor_color);
+            self.canvas.draw_rect(get_rect(point, (1, 1)))?;
         }
 
         self.canvas.present();
@@ -340,3 +532,19 @@ impl Renderer {
         }
     }
 }
+
+/*With unsafe_textures, textures are not freed on their own, so the texture is destroyed
+here, while the canvas that created it is still alive.*/
+impl<T: RenderTarget> Drop for Renderer<T> {
+    fn drop(&mut self) {
+        if let Some(texture) = self.texture_opt.take() {
+            // SAFETY: destroy needs the renderer that created the texture to be alive.
+            // create_texture only ever gets the canvas stored in this Renderer: build makes
+            // the texture after the canvas_command has run, from the canvas it returned,
+            // and build_offscreen from its own surface canvas. The canvas field is private
+            // and never replaced, and drop runs before the fields are dropped, so that
+            // canvas is still alive here. take leaves nothing behind to use afterwards.
+            unsafe { texture.destroy() }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use conways_game_of_life_sdl::{
+    render::{
+        CameraOpt, CyclicalModulatorOpt, DrawOption, RenderMode, RendererBuilder, Rgb, Rygcbm,
+    },
+    Grid, Grid2dVec, Soup,
+};
+
+/*A 4x3 grid drawn at zoom 2 into 12x10 pixels, the camera on cell (2, 1), so cell (x, y)
+covers the pixels from (2x + 2, 2y + 3). Each character is a pixel: '.' the background,
+' ' the grid background, '#' a live cell and 'c' the cursor's outline.*/
+const EXPECTED: [&str; 10] = [
+    "............",
+    "............",
+    "............",
+    "..##  cc  ..",
+    "..##  cc  ..",
+    "..  ##    ..",
+    "..  ##    ..",
+    "..      ##..",
+    "..      ##..",
+    "............",
+];
+
+fn expected(cell: [u8; 3]) -> Vec<u8> {
+    EXPECTED
+        .iter()
+        .flat_map(|row| row.chars())
+        .flat_map(|pixel| match pixel {
+            '.' => [25, 25, 25],
+            ' ' => [0, 0, 0],
+            '#' => cell,
+            'c' => [255, 255, 255],
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/*Every draw option in both render modes. Dynamic colours start out red, the colour new
+cells take before any update.*/
+#[test]
+fn offscreen_render_matches_known_pixels() {
+    let mut grid = Grid2dVec::empty((4, 3));
+    for point in [(0, 0), (1, 1), (3, 2)] {
+        grid.set_cell(point, true).unwrap();
+    }
+    let draw_opts = [
+        (None, [200, 200, 200]),
+        (Some(dynamic_draw_options()[0]), [255, 0, 0]),
+        (Some(dynamic_draw_options()[1]), [255, 0, 0]),
+    ];
+    for (draw_opt, cell) in draw_opts {
+        for render_mode in [RenderMode::Rects, RenderMode::Texture] {
+            let mut builder = RendererBuilder::offscreen();
+            builder.camera_opt = CameraOpt::Position { x: 2., y: 1. };
+            builder.render_mode = render_mode;
+            if let Some(draw_opt) = draw_opt {
+                builder.draw_opt = draw_opt;
+            }
+            let mut renderer = builder.build_offscreen(grid.size(), (12, 10)).unwrap();
+            renderer.camera.zoom(1);
+            assert_eq!(
+                renderer.map_window_pos_to_cell((6, 3), grid.size()),
+                Some((2, 0))
+            );
+            renderer.render_with_cursor(&grid, Some((6, 3))).unwrap();
+            assert_eq!(renderer.size().unwrap(), (12, 10));
+            assert_eq!(
+                renderer.read_pixels().unwrap(),
+                expected(cell),
+                "{:?} {:?}",
+                render_mode,
+                cell
+            );
+        }
+    }
+}
+
+/*The two render modes draw the same pixels for every draw option as a soup evolves,
+with part of the grid out of view.*/
+#[test]
+fn render_modes_agree() {
+    let mut draw_opts = vec![None];
+    draw_opts.extend(dynamic_draw_options().iter().copied().map(Some));
+    for draw_opt in draw_opts {
+        let mut grid = Grid2dVec::empty((30, 20));
+        Soup::new().seed(9).fill(&mut grid).unwrap();
+        let mut renderers: Vec<_> = [RenderMode::Rects, RenderMode::Texture]
+            .iter()
+            .map(|&render_mode| {
+                let mut builder = RendererBuilder::offscreen();
+                builder.camera_opt = CameraOpt::Position { x: 12., y: 8. };
+                builder.render_mode = render_mode;
+                if let Some(draw_opt) = draw_opt {
+                    builder.draw_opt = draw_opt;
+                }
+                let mut renderer = builder.build_offscreen(grid.size(), (64, 48)).unwrap();
+                renderer.camera.zoom(2);
+                renderer
+            })
+            .collect();
+        for generation in 0..12 {
+            let pixels: Vec<Vec<u8>> = renderers
+                .iter_mut()
+                .map(|renderer| {
+                    renderer.render_with_cursor(&grid, Some((20, 20))).unwrap();
+                    renderer.update();
+                    renderer.read_pixels().unwrap()
+                })
+                .collect();
+            assert_eq!(pixels[0], pixels[1], "generation {}", generation);
+            grid.update();
+        }
+    }
+}
+
+fn dynamic_draw_options() -> [DrawOption; 2] {
+    [
+        DrawOption::DynamicCyclical(CyclicalModulatorOpt::Rygcbm(Rygcbm::Red)),
+        DrawOption::DynamicHeatMap {
+            hot: Rgb::Red,
+            cold: Rgb::Blue,
+        },
+    ]
+}
+
+/*The culled renderer sees 10x10 of the 40x40 grid, starting on the top left corner while
+the soup evolves and then panning to the bottom right one. Its pixels must match the same
+corner of a renderer that sees the whole grid all along, so cells born out of view still
+took their colours when they were born.*/
+#[test]
+fn culled_render_keeps_dynamic_colours() {
+    const ZOOM: usize = 4;
+    for render_mode in [RenderMode::Rects, RenderMode::Texture] {
+        for draw_opt in dynamic_draw_options() {
+            let mut grid = Grid2dVec::empty((40, 40));
+            Soup::new().seed(5).fill(&mut grid).unwrap();
+            let build = |(x, y): (f64, f64), window: u32| {
+                let mut builder = RendererBuilder::offscreen();
+                builder.camera_opt = CameraOpt::Position { x, y };
+                builder.render_mode = render_mode;
+                builder.draw_opt = draw_opt;
+                let mut renderer = builder
+                    .build_offscreen(grid.size(), (window, window))
+                    .unwrap();
+                renderer.camera.zoom(ZOOM as i32 - 1);
+                renderer
+            };
+            let mut culled = build((5., 5.), 40);
+            let mut whole = build((20., 20.), 160);
+
+            for _ in 0..20 {
+                culled.render_with_cursor(&grid, None).unwrap();
+                whole.render_with_cursor(&grid, None).unwrap();
+                grid.update();
+                culled.update();
+                whole.update();
+            }
+            culled
+                .camera
+                .move_focus((30 * ZOOM) as f64, (30 * ZOOM) as f64);
+            culled.render_with_cursor(&grid, None).unwrap();
+            whole.render_with_cursor(&grid, None).unwrap();
+
+            /*Cell (x, y) is drawn from pixel 4x in the whole view and 4x - 120 in the
+            culled one.*/
+            let whole_pixels = whole.read_pixels().unwrap();
+            let corner: Vec<u8> = whole_pixels
+                .chunks(160 * 3)
+                .skip(120)
+                .flat_map(|row| row[120 * 3..].iter().copied())
+                .collect();
+            assert_eq!(culled.read_pixels().unwrap(), corner, "{:?}", render_mode);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use conways_game_of_life_tui::{
+    catalog,
+    render::{CyclicalModulatorOpt, DrawOption, Rygcbm},
+    Grid, Grid2dVec, GridUnit, StampMode, TuiInterfaceBuilder,
+};
+use std::{env, error::Error, io};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    const WIDTH: GridUnit = 256;
+    const HEIGHT: GridUnit = 256;
+    let entry = match env::args().nth(1).as_deref().map(catalog::find) {
+        Some(Some(entry)) => entry,
+        Some(None) | None => {
+            println!("Usage: catalog <pattern name>");
+            return Ok(catalog::write_list(io::stdout())?);
+        }
+    };
+
+    let pattern = entry.pattern();
+    let (width, height) = pattern.size();
+    let mut grid = Grid2dVec::empty((WIDTH, HEIGHT));
+    grid.stamp(
+        &pattern,
+        (
+            WIDTH.saturating_sub(width) / 2,
+            HEIGHT.saturating_sub(height) / 2,
+        ),
+        StampMode::Or,
+    )?;
+
+    let mut interface_builder = TuiInterfaceBuilder::new();
+    interface_builder.renderer_builder.draw_opt =
+        DrawOption::DynamicCyclical(CyclicalModulatorOpt::Rygcbm(Rygcbm::Red));
+    interface_builder.build(grid)?.run()?;
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use conways_game_of_life_tui::{
+    render::{DrawOption, Rgb},
+    Backend, Density, GridUnit, Soup, TuiInterfaceBuilder,
+};
+use std::{env, error::Error};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    /*Takes the same arguments as the SDL random bin.*/
+    let mut args = env::args().skip(1);
+    let seed = args.next().filter(|s| s != "-");
+    let backend: Backend = args.next().map_or(Ok(Backend::default()), |s| s.parse())?;
+    let width: GridUnit = args.next().map_or(Ok(256), |s| s.parse())?;
+    let height: GridUnit = args.next().map_or(Ok(width), |s| s.parse())?;
+    let mut grid = backend.empty((width, height));
+    let mut soup = Soup::new().density(Density::Uniform(0.25));
+    if let Some(seed) = seed {
+        soup = soup.seed(seed.parse()?);
+    }
+    let seed = soup.fill(&mut grid)?;
+
+    let mut interface_builder = TuiInterfaceBuilder::new();
+    interface_builder.renderer_builder.draw_opt = DrawOption::DynamicHeatMap {
+        hot: Rgb::Red,
+        cold: Rgb::Blue,
+    };
+    interface_builder.build(grid)?.run()?;
+    println!("Seed: {}", seed);
+    Ok(())
+}
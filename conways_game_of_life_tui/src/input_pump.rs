@@ -0,0 +1,142 @@
+use crate::terminal::Terminal;
+use std::{collections::VecDeque, io};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Input {
+    Faster,
+    MoveCamera { x: i32, y: i32 },
+    NextGlyphs,
+    OneFrame,
+    Pause,
+    Quit,
+    Reset,
+    Slower,
+    ToggleColor,
+}
+
+/*Turns the bytes typed into inputs. Escape sequences are expected to arrive whole, which
+holds for keys typed by hand.*/
+pub struct InputPump {
+    inputs: VecDeque<Input>,
+}
+
+impl InputPump {
+    pub fn new() -> Self {
+        Self {
+            inputs: VecDeque::new(),
+        }
+    }
+
+    pub fn poll_event(&mut self, terminal: &mut Terminal) -> io::Result<Option<Input>> {
+        if self.inputs.is_empty() {
+            let mut buf = [0; 64];
+            let n = terminal.read(&mut buf)?;
+            self.parse(&buf[..n]);
+        }
+        Ok(self.inputs.pop_front())
+    }
+
+    fn parse(&mut self, mut bytes: &[u8]) {
+        while let Some((&byte, rest)) = bytes.split_first() {
+            bytes = rest;
+            let input = match byte {
+                0x1b => match bytes {
+                    [b'[', key, rest @ ..] | [b'O', key, rest @ ..] => {
+                        bytes = rest;
+                        match key {
+                            b'A' => Input::MoveCamera { x: 0, y: -1 },
+                            b'B' => Input::MoveCamera { x: 0, y: 1 },
+                            b'C' => Input::MoveCamera { x: 1, y: 0 },
+                            b'D' => Input::MoveCamera { x: -1, y: 0 },
+                            _ => continue,
+                        }
+                    }
+                    _ => Input::Quit,
+                },
+                /*Ctrl+C, as raw mode stops it sending a signal.*/
+                0x03 | b'q' => Input::Quit,
+                b' ' => Input::Pause,
+                b'\r' | b'\n' => Input::OneFrame,
+                b'r' => Input::Reset,
+                b'h' => Input::MoveCamera { x: -1, y: 0 },
+                b'j' => Input::MoveCamera { x: 0, y: 1 },
+                b'k' => Input::MoveCamera { x: 0, y: -1 },
+                b'l' => Input::MoveCamera { x: 1, y: 0 },
+                b'H' => Input::MoveCamera { x: -8, y: 0 },
+                b'J' => Input::MoveCamera { x: 0, y: 8 },
+                b'K' => Input::MoveCamera { x: 0, y: -8 },
+                b'L' => Input::MoveCamera { x: 8, y: 0 },
+                b'+' | b'=' => Input::Faster,
+                b'-' => Input::Slower,
+                b'g' => Input::NextGlyphs,
+                b'c' => Input::ToggleColor,
+                _ => continue,
+            };
+            self.inputs.push_back(input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Input, InputPump};
+
+    fn parse(bytes: &[u8]) -> Vec<Input> {
+        let mut input_pump = InputPump::new();
+        input_pump.parse(bytes);
+        input_pump.inputs.into_iter().collect()
+    }
+
+    #[test]
+    fn arrows_in_both_cursor_modes() {
+        let up = Input::MoveCamera { x: 0, y: -1 };
+        let down = Input::MoveCamera { x: 0, y: 1 };
+        let right = Input::MoveCamera { x: 1, y: 0 };
+        let left = Input::MoveCamera { x: -1, y: 0 };
+        assert_eq!(parse(b"\x1b[A\x1b[B\x1b[C\x1b[D"), [up, down, right, left]);
+        assert_eq!(parse(b"\x1bOA\x1bOB\x1bOC\x1bOD"), [up, down, right, left]);
+    }
+
+    #[test]
+    fn escape_sequences_among_keys() {
+        assert_eq!(
+            parse(b"k\x1b[Cg \r"),
+            [
+                Input::MoveCamera { x: 0, y: -1 },
+                Input::MoveCamera { x: 1, y: 0 },
+                Input::NextGlyphs,
+                Input::Pause,
+                Input::OneFrame,
+            ]
+        );
+        /*Unknown sequences are skipped whole, so their final byte is not taken as a key.*/
+        assert_eq!(parse(b"\x1b[Hr\x1bOPq"), [Input::Reset, Input::Quit]);
+    }
+
+    #[test]
+    fn lone_escape_quits() {
+        assert_eq!(parse(b"\x1b"), [Input::Quit]);
+        assert_eq!(parse(b"\x1bc"), [Input::Quit, Input::ToggleColor]);
+        /*A sequence cut short is a lone escape followed by what was left of it.*/
+        assert_eq!(parse(b"\x1b["), [Input::Quit]);
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(
+            parse(b"\x03qHJKL+=-\nx"),
+            [
+                Input::Quit,
+                Input::Quit,
+                Input::MoveCamera { x: -8, y: 0 },
+                Input::MoveCamera { x: 0, y: 8 },
+                Input::MoveCamera { x: 0, y: -8 },
+                Input::MoveCamera { x: 8, y: 0 },
+                Input::Faster,
+                Input::Faster,
+                Input::Slower,
+                Input::OneFrame,
+            ]
+        );
+    }
+}
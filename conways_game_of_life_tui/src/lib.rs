@@ -0,0 +1,149 @@
+mod input_pump;
+pub mod render;
+mod terminal;
+
+pub use conways_game_of_life_impl::{
+    catalog, color, config, BResult, Backend, Density, DynGrid, Grid, Grid1dVec, Grid2dArr,
+    Grid2dVec, GridBitPacked, GridChunked, GridPoint, GridUnit, Pattern, Rule, Simulation, Soup,
+    StampMode, Symmetry, Topology,
+};
+use input_pump::{Input, InputPump};
+use render::{Renderer, RendererBuilder};
+use std::{io, marker::PhantomData, thread, time::Duration};
+use terminal::Terminal;
+
+const PAUSED_FRAME_DURATION: Duration = Duration::from_millis(33);
+
+pub struct TuiInterfaceBuilder<G>
+where
+    G: Grid,
+{
+    pub renderer_builder: RendererBuilder,
+    pub fps: u64,
+    phantom: PhantomData<G>,
+}
+
+impl<G> TuiInterfaceBuilder<G>
+where
+    G: Clone + Grid,
+{
+    pub fn new() -> Self {
+        Self {
+            renderer_builder: RendererBuilder::new(),
+            fps: 30,
+            phantom: PhantomData,
+        }
+    }
+
+    /*Takes over the terminal until the interface is dropped.*/
+    pub fn build(self, grid: G) -> io::Result<TuiInterface<G>> {
+        let mut simulation = Simulation::new(grid.clone());
+        simulation.set_gps(self.fps);
+        Ok(TuiInterface::<G> {
+            terminal: Terminal::new()?,
+            input_pump: InputPump::new(),
+            renderer: self.renderer_builder.build(grid.size()),
+            fps: self.fps,
+            init_grid: grid,
+            simulation,
+            pause: false,
+        })
+    }
+}
+
+impl<G> Default for TuiInterfaceBuilder<G>
+where
+    G: Clone + Grid,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*Controls: space pauses, enter advances one frame, r resets, arrows or hjkl move the view
+(HJKL faster), + and - double or halve the generations per frame, g switches between
+braille and half blocks, c toggles colour and q or escape quits.*/
+pub struct TuiInterface<G>
+where
+    G: Grid,
+{
+    terminal: Terminal,
+    input_pump: InputPump,
+    renderer: Renderer,
+    fps: u64,
+    init_grid: G,
+    simulation: Simulation<G>,
+    pause: bool,
+}
+
+impl<G> TuiInterface<G>
+where
+    G: Clone + Grid,
+{
+    pub fn run(&mut self) -> io::Result<()> {
+        while self.tick()? {}
+        Ok(())
+    }
+
+    pub fn tick(&mut self) -> io::Result<bool> {
+        let mut run = true;
+        let mut one_frame = false;
+        while let Some(input) = self.input_pump.poll_event(&mut self.terminal)? {
+            match input {
+                Input::Faster => self.set_step_exponent(self.simulation.step_exponent() + 1),
+                Input::MoveCamera { x, y } => {
+                    let grid_size = self.simulation.grid().size();
+                    self.renderer.move_camera((x, y), grid_size);
+                }
+                Input::NextGlyphs => self.renderer.next_glyphs(),
+                Input::OneFrame => one_frame = true,
+                Input::Pause => self.pause = !self.pause,
+                Input::Quit => run = false,
+                Input::Reset => {
+                    self.renderer.reset();
+                    *self.simulation.grid_mut() = self.init_grid.clone();
+                }
+                Input::Slower => {
+                    self.set_step_exponent(self.simulation.step_exponent().saturating_sub(1))
+                }
+                Input::ToggleColor => self.renderer.truecolor = !self.renderer.truecolor,
+            }
+        }
+
+        let status = self.status();
+        let frame = self
+            .renderer
+            .render(self.simulation.grid(), self.terminal.size(), &status);
+        self.terminal.write(&frame)?;
+        if !self.pause || one_frame {
+            self.simulation.frame();
+            self.renderer.update();
+        } else {
+            thread::sleep(PAUSED_FRAME_DURATION);
+        }
+        Ok(run)
+    }
+
+    /*Keeps the frame rate while changing how many generations each frame runs.*/
+    fn set_step_exponent(&mut self, step_exponent: u32) {
+        self.simulation.set_step_exponent(step_exponent);
+        let step_exponent = self.simulation.step_exponent();
+        self.simulation.set_gps(self.fps << step_exponent);
+    }
+
+    fn status(&self) -> String {
+        let measured = match self.simulation.measured_gps() {
+            Some(gps) if !self.pause => format!("{:.1}", gps),
+            _ => String::from("-"),
+        };
+        format!(
+            " generation {} | {} gen/frame | {} gen/s | {}{:?} | space pause, enter step, \
+             r reset, arrows move, +/- speed, g glyphs, c colour, q quit",
+            self.simulation.grid().generation(),
+            self.simulation.generations_per_frame(),
+            measured,
+            if self.pause { "paused | " } else { "" },
+            self.renderer.glyphs,
+        )
+    }
+}
@@ -0,0 +1,281 @@
+pub use crate::color::{CyclicalModulatorOpt, Rgb, Rygcbm};
+use crate::{
+    color::{Color, CyclicalModulator, NewCellColorCyclical, NewCellColorHeatMap},
+    Grid, GridPoint, GridUnit,
+};
+use std::fmt::Write;
+
+/*Half blocks fit 1x2 cells in a character and braille 2x4.*/
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Glyphs {
+    HalfBlock,
+    Braille,
+}
+
+impl Glyphs {
+    pub fn cell_size(&self) -> (i32, i32) {
+        match self {
+            Self::HalfBlock => (1, 2),
+            Self::Braille => (2, 4),
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            Self::HalfBlock => Self::Braille,
+            Self::Braille => Self::HalfBlock,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum DrawOption {
+    Static(Color),
+    DynamicCyclical(CyclicalModulatorOpt),
+    DynamicHeatMap { hot: Rgb, cold: Rgb },
+}
+
+enum DrawOptionPrivate {
+    Static(Color),
+    DynamicCyclical(NewCellColorCyclical),
+    DynamicHeatMap(NewCellColorHeatMap),
+}
+
+/*Colours are only written in truecolor mode; otherwise live cells take the terminal's
+foreground colour.*/
+pub struct RendererBuilder {
+    pub glyphs: Glyphs,
+    pub truecolor: bool,
+    pub draw_opt: DrawOption,
+}
+
+impl RendererBuilder {
+    /*Truecolor is on when COLORTERM says the terminal supports it.*/
+    pub fn new() -> Self {
+        Self {
+            glyphs: Glyphs::Braille,
+            truecolor: std::env::var("COLORTERM").is_ok_and(|s| s == "truecolor" || s == "24bit"),
+            draw_opt: DrawOption::Static(Color::rgb(200, 200, 200)),
+        }
+    }
+
+    pub fn build(self, grid_size: GridPoint) -> Renderer {
+        Renderer {
+            camera: (grid_size.0 as i32 / 2, grid_size.1 as i32 / 2),
+            glyphs: self.glyphs,
+            truecolor: self.truecolor,
+            draw_opt: match self.draw_opt {
+                DrawOption::Static(color) => DrawOptionPrivate::Static(color),
+                DrawOption::DynamicCyclical(opt) => DrawOptionPrivate::DynamicCyclical(
+                    NewCellColorCyclical::new(CyclicalModulator::new(opt), grid_size),
+                ),
+                DrawOption::DynamicHeatMap { hot, cold } => DrawOptionPrivate::DynamicHeatMap(
+                    NewCellColorHeatMap::new(hot, cold, grid_size),
+                ),
+            },
+        }
+    }
+}
+
+impl Default for RendererBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*Tracks the colours last written so escape sequences are only sent when they change.*/
+struct Pen {
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+impl Pen {
+    fn set(&mut self, frame: &mut String, fg: Option<Color>, bg: Option<Color>) {
+        if fg != self.fg {
+            match fg {
+                Some(c) => write!(frame, "\x1b[38;2;{};{};{}m", c.r, c.g, c.b),
+                None => write!(frame, "\x1b[39m"),
+            }
+            .unwrap();
+            self.fg = fg;
+        }
+        if bg != self.bg {
+            match bg {
+                Some(c) => write!(frame, "\x1b[48;2;{};{};{}m", c.r, c.g, c.b),
+                None => write!(frame, "\x1b[49m"),
+            }
+            .unwrap();
+            self.bg = bg;
+        }
+    }
+}
+
+fn average(colors: &[Color]) -> Option<Color> {
+    if colors.is_empty() {
+        return None;
+    }
+    let sum = |f: fn(&Color) -> u8| {
+        (colors.iter().map(|c| f(c) as usize).sum::<usize>() / colors.len()) as u8
+    };
+    Some(Color::rgb(sum(|c| c.r), sum(|c| c.g), sum(|c| c.b)))
+}
+
+pub struct Renderer {
+    /*The cell at the centre of the view.*/
+    pub camera: (i32, i32),
+    pub glyphs: Glyphs,
+    pub truecolor: bool,
+    draw_opt: DrawOptionPrivate,
+}
+
+impl Renderer {
+    pub fn next_glyphs(&mut self) {
+        self.glyphs = self.glyphs.next();
+    }
+
+    /*Moves the camera by whole characters, keeping it on the grid.*/
+    pub fn move_camera(&mut self, (x, y): (i32, i32), grid_size: GridPoint) {
+        let cell_size = self.glyphs.cell_size();
+        self.camera = (
+            (self.camera.0 + x * cell_size.0).clamp(0, grid_size.0 as i32),
+            (self.camera.1 + y * cell_size.1).clamp(0, grid_size.1 as i32),
+        );
+    }
+
+    fn cell_color<G: Grid>(&mut self, grid: &G, point: GridPoint) -> Option<Color> {
+        let cell = grid.get_cell_unchecked(point);
+        let color = match &mut self.draw_opt {
+            DrawOptionPrivate::Static(color) => *color,
+            DrawOptionPrivate::DynamicCyclical(ncc) => ncc.get_cell_color(point, cell),
+            DrawOptionPrivate::DynamicHeatMap(ncc) => ncc.get_cell_color(point, cell),
+        };
+        if cell {
+            Some(color)
+        } else {
+            None
+        }
+    }
+
+    /*Draws the grid over all but the last of the given rows of the terminal and the
+    status line on the last, returning the escape sequences to write.*/
+    pub fn render<G: Grid>(
+        &mut self,
+        grid: &G,
+        (columns, rows): (u16, u16),
+        status: &str,
+    ) -> String {
+        let grid_size = grid.size();
+        let cell_size = self.glyphs.cell_size();
+        let view = (columns as i32, rows.saturating_sub(1) as i32);
+        let view_cells = (view.0 * cell_size.0, view.1 * cell_size.1);
+        let clamp_origin =
+            |camera: i32, view: i32, grid: i32| (camera - view / 2).min(grid - view).max(0);
+        let origin = (
+            clamp_origin(self.camera.0, view_cells.0, grid_size.0 as i32),
+            clamp_origin(self.camera.1, view_cells.1, grid_size.1 as i32),
+        );
+
+        /*Dynamic colours are taken when a cell is born, so cells out of view are still
+        visited to keep their colours right, but not drawn.*/
+        if !matches!(self.draw_opt, DrawOptionPrivate::Static(_)) {
+            let in_view = |x: GridUnit, origin: i32, view: i32| {
+                origin <= x as i32 && (x as i32) < origin + view
+            };
+            grid.inspect(|(x, y), grid| {
+                if !in_view(x, origin.0, view_cells.0) || !in_view(y, origin.1, view_cells.1) {
+                    self.cell_color(grid, (x, y));
+                }
+            });
+        }
+
+        let mut frame = String::new();
+        let mut pen = Pen { fg: None, bg: None };
+        frame.push_str("\x1b[0m");
+        let mut colors = Vec::with_capacity(8);
+        for row in 0..view.1 {
+            write!(frame, "\x1b[{};1H", row + 1).unwrap();
+            for column in 0..view.0 {
+                let top_left = (
+                    origin.0 + column * cell_size.0,
+                    origin.1 + row * cell_size.1,
+                );
+                let mut bits = 0u8;
+                colors.clear();
+                let mut halves = [None, None];
+                for dy in 0..cell_size.1 {
+                    for dx in 0..cell_size.0 {
+                        let (x, y) = (top_left.0 + dx, top_left.1 + dy);
+                        if x >= grid_size.0 as i32 || y >= grid_size.1 as i32 {
+                            continue;
+                        }
+                        if let Some(color) = self.cell_color(grid, (x as u16, y as u16)) {
+                            /*Braille numbers the dots down the left column, down the right,
+                            then the bottom row left to right.*/
+                            bits |= match (dx, dy) {
+                                (0, 3) => 0x40,
+                                (1, 3) => 0x80,
+                                (0, dy) => 1 << dy,
+                                (_, dy) => 1 << (dy + 3),
+                            };
+                            colors.push(color);
+                            halves[dy as usize % 2] = Some(color);
+                        }
+                    }
+                }
+
+                let color = |c: Option<Color>| if self.truecolor { c } else { None };
+                match self.glyphs {
+                    Glyphs::Braille => {
+                        pen.set(&mut frame, color(average(&colors)), None);
+                        let glyph = match bits {
+                            0 => ' ',
+                            bits => char::from_u32(0x2800 + bits as u32).unwrap_or(' '),
+                        };
+                        frame.push(glyph);
+                    }
+                    Glyphs::HalfBlock => {
+                        let (glyph, fg, bg) = match halves {
+                            [None, None] => (' ', None, None),
+                            [Some(top), None] => ('▀', color(Some(top)), None),
+                            [None, Some(bottom)] => ('▄', color(Some(bottom)), None),
+                            [Some(top), Some(bottom)] if !self.truecolor || top == bottom => {
+                                ('█', color(Some(top)), None)
+                            }
+                            [Some(top), Some(bottom)] => ('▀', Some(top), Some(bottom)),
+                        };
+                        pen.set(&mut frame, fg, bg);
+                        frame.push(glyph);
+                    }
+                }
+            }
+        }
+
+        pen.set(&mut frame, None, None);
+        let status: String = status.chars().take(columns as usize).collect();
+        write!(
+            frame,
+            "\x1b[{};1H\x1b[7m{:<width$}\x1b[0m",
+            rows,
+            status,
+            width = columns as usize
+        )
+        .unwrap();
+        frame
+    }
+
+    pub fn update(&mut self) {
+        match &mut self.draw_opt {
+            DrawOptionPrivate::DynamicCyclical(ncc) => ncc.update(),
+            DrawOptionPrivate::DynamicHeatMap(ncc) => ncc.update(),
+            _ => (),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        match &mut self.draw_opt {
+            DrawOptionPrivate::DynamicCyclical(ncc) => ncc.reset(),
+            DrawOptionPrivate::DynamicHeatMap(ncc) => ncc.reset(),
+            _ => (),
+        }
+    }
+}
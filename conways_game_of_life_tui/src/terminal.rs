@@ -0,0 +1,74 @@
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+/*Puts the terminal in raw mode on the alternate screen with the cursor hidden, and puts
+everything back when dropped. Reads never block.*/
+pub struct Terminal {
+    original: libc::termios,
+    stdout: io::Stdout,
+}
+
+impl Terminal {
+    pub fn new() -> io::Result<Self> {
+        let mut original: libc::termios = unsafe { mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut raw = original;
+        unsafe { libc::cfmakeraw(&mut raw) };
+        raw.c_cc[libc::VMIN] = 0;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut ret = Self {
+            original,
+            stdout: io::stdout(),
+        };
+        ret.write("\x1b[?1049h\x1b[?25l\x1b[2J")?;
+        Ok(ret)
+    }
+
+    /*Columns and rows, or 80 by 24 if the terminal does not say.*/
+    pub fn size(&self) -> (u16, u16) {
+        let mut winsize: libc::winsize = unsafe { mem::zeroed() };
+        if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut winsize) } == 0
+            && winsize.ws_col > 0
+            && winsize.ws_row > 0
+        {
+            (winsize.ws_col, winsize.ws_row)
+        } else {
+            (80, 24)
+        }
+    }
+
+    /*Returns the bytes already typed, which may be none.*/
+    pub fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+        if n < 0 {
+            let e = io::Error::last_os_error();
+            match e.kind() {
+                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock => Ok(0),
+                _ => Err(e),
+            }
+        } else {
+            Ok(n as usize)
+        }
+    }
+
+    pub fn write(&mut self, s: &str) -> io::Result<()> {
+        let mut lock = self.stdout.lock();
+        lock.write_all(s.as_bytes())?;
+        lock.flush()
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = self.write("\x1b[0m\x1b[?25h\x1b[?1049l");
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original) };
+    }
+}
@@ -0,0 +1,218 @@
+use conways_game_of_life_tui::{
+    color::Color,
+    render::{DrawOption, Glyphs, Renderer, RendererBuilder, Rgb},
+    Grid, Grid1dVec, GridPoint,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Char {
+    glyph: char,
+    fg: Option<Color>,
+    bg: Option<Color>,
+}
+
+/*Plays a frame back as a terminal would, as rows of characters with their colours. Only
+the sequences the renderer writes are understood.*/
+fn screen(frame: &str) -> Vec<Vec<Char>> {
+    let mut rows: Vec<Vec<Char>> = Vec::new();
+    let (mut fg, mut bg) = (None, None);
+    let mut chars = frame.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            rows.last_mut()
+                .expect("text before the cursor was placed")
+                .push(Char { glyph: c, fg, bg });
+            continue;
+        }
+        assert_eq!(chars.next(), Some('['));
+        let mut params = String::new();
+        let end = loop {
+            match chars.next().unwrap() {
+                c if c.is_ascii_alphabetic() => break c,
+                c => params.push(c),
+            }
+        };
+        let numbers: Vec<u8> = params.split(';').map(|n| n.parse().unwrap()).collect();
+        match (end, numbers.as_slice()) {
+            ('H', [row, 1]) => {
+                assert_eq!(*row as usize, rows.len() + 1);
+                rows.push(Vec::new());
+            }
+            ('m', [0]) => (fg, bg) = (None, None),
+            ('m', [7]) => (),
+            ('m', [39]) => fg = None,
+            ('m', [49]) => bg = None,
+            ('m', [38, 2, r, g, b]) => fg = Some(Color::rgb(*r, *g, *b)),
+            ('m', [48, 2, r, g, b]) => bg = Some(Color::rgb(*r, *g, *b)),
+            _ => panic!("unexpected sequence {:?}{}", params, end),
+        }
+    }
+    rows
+}
+
+fn glyphs(row: &[Char]) -> String {
+    row.iter().map(|c| c.glyph).collect()
+}
+
+fn renderer(glyphs: Glyphs, truecolor: bool, draw_opt: DrawOption, size: GridPoint) -> Renderer {
+    let mut builder = RendererBuilder::new();
+    builder.glyphs = glyphs;
+    builder.truecolor = truecolor;
+    builder.draw_opt = draw_opt;
+    builder.build(size)
+}
+
+fn grid(size: GridPoint, cells: &[GridPoint]) -> Grid1dVec {
+    let mut grid = Grid1dVec::empty(size);
+    for &point in cells {
+        grid.set_cell(point, true).unwrap();
+    }
+    grid
+}
+
+const WHITE: Color = Color::rgb(255, 255, 255);
+const HEAT_MAP: DrawOption = DrawOption::DynamicHeatMap {
+    hot: Rgb::Red,
+    cold: Rgb::Blue,
+};
+
+#[test]
+fn braille_dots() {
+    /*Dot numbering: down the left column, down the right, then the bottom row.*/
+    let bits = [
+        ((0, 0), 0x01),
+        ((0, 1), 0x02),
+        ((0, 2), 0x04),
+        ((1, 0), 0x08),
+        ((1, 1), 0x10),
+        ((1, 2), 0x20),
+        ((0, 3), 0x40),
+        ((1, 3), 0x80),
+    ];
+    let mut all = Vec::new();
+    for &(point, bit) in &bits {
+        all.push(point);
+        let grid = grid((2, 4), &[point]);
+        let frame = renderer(Glyphs::Braille, false, DrawOption::Static(WHITE), (2, 4)).render(
+            &grid,
+            (1, 2),
+            "",
+        );
+        assert_eq!(
+            glyphs(&screen(&frame)[0]),
+            char::from_u32(0x2800 + bit).unwrap().to_string(),
+            "{:?}",
+            point
+        );
+    }
+    let frame = renderer(Glyphs::Braille, false, DrawOption::Static(WHITE), (2, 4)).render(
+        &grid((2, 4), &all),
+        (1, 2),
+        "",
+    );
+    assert_eq!(glyphs(&screen(&frame)[0]), "\u{28ff}");
+    let frame = renderer(Glyphs::Braille, false, DrawOption::Static(WHITE), (2, 4)).render(
+        &grid((2, 4), &[]),
+        (1, 2),
+        "",
+    );
+    assert_eq!(glyphs(&screen(&frame)[0]), " ");
+}
+
+#[test]
+fn half_blocks() {
+    let white = Some(WHITE);
+    for truecolor in [false, true] {
+        let fg = if truecolor { white } else { None };
+        let cases: [(&[GridPoint], char); 4] = [
+            (&[], ' '),
+            (&[(0, 0)], '▀'),
+            (&[(0, 1)], '▄'),
+            (&[(0, 0), (0, 1)], '█'),
+        ];
+        for (cells, glyph) in cases {
+            let frame = renderer(
+                Glyphs::HalfBlock,
+                truecolor,
+                DrawOption::Static(WHITE),
+                (1, 2),
+            )
+            .render(&grid((1, 2), cells), (1, 2), "");
+            let expected_fg = if cells.is_empty() { None } else { fg };
+            assert_eq!(
+                screen(&frame)[0],
+                vec![Char {
+                    glyph,
+                    fg: expected_fg,
+                    bg: None
+                }],
+                "{:?} truecolor {}",
+                cells,
+                truecolor
+            );
+        }
+    }
+}
+
+/*Cells of different colours sharing a character need the background for the bottom one,
+which only truecolor can give.*/
+#[test]
+fn half_blocks_of_two_colours() {
+    for truecolor in [false, true] {
+        let mut renderer = renderer(Glyphs::HalfBlock, truecolor, HEAT_MAP, (1, 2));
+        renderer.render(&grid((1, 2), &[(0, 0)]), (1, 2), "");
+        for _ in 0..10 {
+            renderer.update();
+        }
+        let frame = renderer.render(&grid((1, 2), &[(0, 0), (0, 1)]), (1, 2), "");
+        let expected = if truecolor {
+            Char {
+                glyph: '▀',
+                fg: Some(Color::rgb(245, 0, 10)),
+                bg: Some(Color::rgb(255, 0, 0)),
+            }
+        } else {
+            Char {
+                glyph: '█',
+                fg: None,
+                bg: None,
+            }
+        };
+        assert_eq!(screen(&frame)[0], vec![expected]);
+    }
+}
+
+#[test]
+fn status_line_is_cut_and_padded_to_the_width() {
+    let mut renderer = renderer(Glyphs::HalfBlock, false, DrawOption::Static(WHITE), (5, 2));
+    let grid = grid((5, 2), &[]);
+    let rows = screen(&renderer.render(&grid, (5, 2), "generation 12"));
+    assert_eq!(rows.len(), 2);
+    assert_eq!(glyphs(&rows[1]), "gener");
+    let rows = screen(&renderer.render(&grid, (5, 2), "gen"));
+    assert_eq!(glyphs(&rows[1]), "gen  ");
+}
+
+/*A cell born out of view takes its colour when it is born, not when it comes into view,
+so it has cooled by the time the camera reaches it.*/
+#[test]
+fn cells_out_of_view_keep_their_colours() {
+    let grid = grid((40, 2), &[(30, 0)]);
+    let mut renderer = renderer(Glyphs::HalfBlock, true, HEAT_MAP, grid.size());
+    renderer.camera = (2, 1);
+    let rows = screen(&renderer.render(&grid, (4, 2), ""));
+    assert_eq!(glyphs(&rows[0]), "    ");
+    for _ in 0..5 {
+        renderer.update();
+    }
+    renderer.camera = (30, 1);
+    let rows = screen(&renderer.render(&grid, (4, 2), ""));
+    assert_eq!(
+        rows[0][2],
+        Char {
+            glyph: '▀',
+            fg: Some(Color::rgb(250, 0, 5)),
+            bg: None
+        }
+    );
+}